@@ -0,0 +1,256 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::PlayerCombatAction;
+use crate::highscores;
+use crate::monster::MonsterSpecies;
+use crate::player::Espece;
+use crate::storage;
+use crate::t;
+
+// Succès : évalués à partir des événements de la partie, conservés dans profile.jsonl.
+// Comme pour les scores, le profil n'est fait que de lignes ajoutées sous verrou :
+// un succès débloqué ou une victoire par ligne.
+
+const FILE_NAME: &str = "profile.jsonl";
+// niveau minimum du dragon pour « Sang-froid »
+const DRAGON_LEVEL: u32 = 3;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Achievement {
+    FirstVictory,
+    AllSpecies,
+    DragonNoPotion,
+    Fireproof,
+    BasicOnly,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstVictory,
+        Achievement::AllSpecies,
+        Achievement::DragonNoPotion,
+        Achievement::Fireproof,
+        Achievement::BasicOnly,
+    ];
+
+    // clé du catalogue de textes, suivie de .name ou .description
+    fn key(&self) -> &'static str {
+        match self {
+            Achievement::FirstVictory => "achievement.first_victory",
+            Achievement::AllSpecies => "achievement.all_species",
+            Achievement::DragonNoPotion => "achievement.dragon_no_potion",
+            Achievement::Fireproof => "achievement.fireproof",
+            Achievement::BasicOnly => "achievement.basic_only",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        t!(&format!("{}.name", self.key()))
+    }
+
+    pub fn description(&self) -> String {
+        t!(&format!("{}.description", self.key()), level = DRAGON_LEVEL)
+    }
+}
+
+// ce que la partie signale au suivi des succès
+#[derive(Clone, Copy)]
+pub enum GameEvent {
+    FightStarted,
+    PlayerAction(PlayerCombatAction),
+    FightWon { species: MonsterSpecies, level: u32 },
+    FireStepped,
+    FloorReached { level: u32, last: bool },
+    Victory(Espece),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ProfileLine {
+    Unlocked { achievement: Achievement, date: String },
+    Victory { espece: String },
+}
+
+#[derive(Default)]
+pub struct Achievements {
+    unlocked: Vec<(Achievement, String)>,
+    victories: Vec<Espece>,
+    // lignes pas encore écrites dans le profil
+    pending: Vec<ProfileLine>,
+    // combat en cours
+    fight_basic_only: bool,
+    fight_potion: bool,
+    touched_fire: bool,
+}
+
+impl Achievements {
+    pub fn load() -> io::Result<Self> {
+        let mut achievements = Achievements::default();
+        for line in storage::read_lines(&storage::data_file(FILE_NAME)?)? {
+            match serde_json::from_str(&line) {
+                Ok(ProfileLine::Unlocked { achievement, date }) => {
+                    if !achievements.is_unlocked(achievement) {
+                        achievements.unlocked.push((achievement, date));
+                    }
+                },
+                Ok(ProfileLine::Victory { espece }) => {
+                    if let Some(espece) = Espece::parse(&espece) {
+                        if !achievements.victories.contains(&espece) {
+                            achievements.victories.push(espece);
+                        }
+                    }
+                },
+                Err(_) => {},
+            }
+        }
+        Ok(achievements)
+    }
+
+    // écrit les nouveaux succès et victoires dans le profil
+    pub fn save(&mut self) -> io::Result<()> {
+        let path = storage::data_file(FILE_NAME)?;
+        while let Some(line) = self.pending.first() {
+            storage::append_line(&path, &serde_json::to_string(line).map_err(io::Error::other)?)?;
+            self.pending.remove(0);
+        }
+        Ok(())
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.iter().any(|(unlocked, _)| *unlocked == achievement)
+    }
+
+    // met à jour le suivi et renvoie les succès débloqués par cet événement
+    pub fn observe(&mut self, event: GameEvent) -> Vec<Achievement> {
+        let mut earned = Vec::new();
+        match event {
+            GameEvent::FightStarted => {
+                self.fight_basic_only = true;
+                self.fight_potion = false;
+            },
+            GameEvent::PlayerAction(action) => {
+                if action != PlayerCombatAction::Attack {
+                    self.fight_basic_only = false;
+                }
+                if action == PlayerCombatAction::Drink {
+                    self.fight_potion = true;
+                }
+            },
+            GameEvent::FightWon { species, level } => {
+                if self.fight_basic_only {
+                    earned.push(Achievement::BasicOnly);
+                }
+                if species == MonsterSpecies::Dragon && level >= DRAGON_LEVEL && !self.fight_potion {
+                    earned.push(Achievement::DragonNoPotion);
+                }
+            },
+            GameEvent::FireStepped => self.touched_fire = true,
+            GameEvent::FloorReached { last, .. } => {
+                if last && !self.touched_fire {
+                    earned.push(Achievement::Fireproof);
+                }
+            },
+            GameEvent::Victory(espece) => {
+                earned.push(Achievement::FirstVictory);
+                if !self.victories.contains(&espece) {
+                    self.victories.push(espece);
+                    self.pending.push(ProfileLine::Victory { espece: espece.id().to_string() });
+                }
+                if Espece::ALL.iter().all(|espece| self.victories.contains(espece)) {
+                    earned.push(Achievement::AllSpecies);
+                }
+            },
+        }
+
+        earned.retain(|achievement| !self.is_unlocked(*achievement));
+        for achievement in &earned {
+            let date = highscores::today();
+            self.unlocked.push((*achievement, date.clone()));
+            self.pending.push(ProfileLine::Unlocked { achievement: *achievement, date });
+        }
+        earned
+    }
+
+    // une ligne par succès pour le menu
+    pub fn menu_lines(&self) -> Vec<String> {
+        let mut lines = vec![t!("achievements.count", unlocked = self.unlocked.len(), total = Achievement::ALL.len()), String::new()];
+        for achievement in Achievement::ALL {
+            let date = self.unlocked.iter().find(|(unlocked, _)| *unlocked == achievement).map(|(_, date)| date);
+            match date {
+                Some(date) => lines.push(format!("🏅 {:<18} {} ({})", achievement.name(), achievement.description(), date)),
+                None => lines.push(format!("🔒 {:<18} {}", achievement.name(), achievement.description())),
+            }
+        }
+        let victories: Vec<String> = self.victories.iter().map(|espece| espece.name()).collect();
+        lines.push(String::new());
+        lines.push(t!("achievements.victories", especes = if victories.is_empty() { t!("achievements.no_victory") } else { victories.join(", ") }));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fight(achievements: &mut Achievements, actions: &[PlayerCombatAction], species: MonsterSpecies, level: u32) -> Vec<Achievement> {
+        achievements.observe(GameEvent::FightStarted);
+        for &action in actions {
+            achievements.observe(GameEvent::PlayerAction(action));
+        }
+        achievements.observe(GameEvent::FightWon { species, level })
+    }
+
+    #[test]
+    fn basic_attacks_only_unlock_basic_only() {
+        let mut achievements = Achievements::default();
+        let earned = fight(&mut achievements, &[PlayerCombatAction::Attack, PlayerCombatAction::Attack], MonsterSpecies::Goblin, 1);
+        assert_eq!(earned, [Achievement::BasicOnly]);
+        assert!(achievements.is_unlocked(Achievement::BasicOnly));
+        // un succès ne se débloque qu'une fois
+        assert!(fight(&mut achievements, &[PlayerCombatAction::Attack], MonsterSpecies::Goblin, 1).is_empty());
+    }
+
+    #[test]
+    fn special_or_potion_spoils_basic_only() {
+        for action in [PlayerCombatAction::SpecialAttack, PlayerCombatAction::Drink] {
+            let mut achievements = Achievements::default();
+            let earned = fight(&mut achievements, &[PlayerCombatAction::Attack, action], MonsterSpecies::Orc, 2);
+            assert!(earned.is_empty(), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn potion_only_spoils_its_own_fight() {
+        let mut achievements = Achievements::default();
+        let earned = fight(&mut achievements, &[PlayerCombatAction::Drink, PlayerCombatAction::Attack], MonsterSpecies::Dragon, DRAGON_LEVEL);
+        assert!(earned.is_empty());
+
+        // le combat suivant repart de zéro : la potion d'avant ne compte plus
+        let earned = fight(&mut achievements, &[PlayerCombatAction::SpecialAttack], MonsterSpecies::Dragon, DRAGON_LEVEL);
+        assert_eq!(earned, [Achievement::DragonNoPotion]);
+    }
+
+    #[test]
+    fn weak_dragon_does_not_count() {
+        let mut achievements = Achievements::default();
+        let earned = fight(&mut achievements, &[PlayerCombatAction::SpecialAttack], MonsterSpecies::Dragon, DRAGON_LEVEL - 1);
+        assert!(earned.is_empty());
+    }
+
+    #[test]
+    fn fireproof_and_victories() {
+        let mut achievements = Achievements::default();
+        assert!(achievements.observe(GameEvent::FloorReached { level: 2, last: false }).is_empty());
+        achievements.observe(GameEvent::FireStepped);
+        assert!(achievements.observe(GameEvent::FloorReached { level: 5, last: true }).is_empty());
+
+        let mut earned = Vec::new();
+        for espece in Espece::ALL {
+            earned.extend(achievements.observe(GameEvent::Victory(espece)));
+        }
+        assert_eq!(earned, [Achievement::FirstVictory, Achievement::AllSpecies]);
+    }
+}
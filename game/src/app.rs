@@ -0,0 +1,968 @@
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+
+use crate::achievements::Achievements;
+use crate::clock::SpawnTiming;
+use crate::error::Result;
+use crate::game::{CombatMode, Difficulty, Game, GameState, PlayerCombatAction, DEFAULT_MAP_HEIGHT, DEFAULT_MAP_WIDTH, LAST_LEVEL};
+use crate::highscores::{self, HighScore};
+use crate::i18n::{self, Locale};
+use crate::keys::{Action, Bindings};
+use crate::log::MessageLog;
+use crate::morgue;
+use crate::pacing::{Pacer, Speed};
+use crate::player::{self, Espece, Player};
+use crate::replay::{Command, Replay};
+use crate::save;
+use crate::t;
+use crate::tactical::TacticalAction;
+use crate::tileset::Tileset;
+use crate::ui::{Overlay, Screen, View};
+
+// Machine à états de l'interface : chaque écran (menus, partie, pause, fiche...) a sa
+// propre gestion des touches et son propre affichage. La partie garde son GameState
+// (exploration, combat, visée) ; l'interface ne fait que l'encadrer.
+
+// durée d'un tick de l'horloge en mode temps réel
+const TICK_DURATION: Duration = Duration::from_secs(1);
+// attente maximale d'une touche avant de vérifier l'horloge
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+// lignes remontées dans le journal par page précédente
+const LOG_SCROLL_STEP: usize = 5;
+// nombre de lignes du tableau des scores
+const HIGH_SCORES_SHOWN: usize = 10;
+// tailles de carte proposées dans les options
+const MAP_SIZES: [(usize, usize); 5] = [(10, 10), (20, 15), (40, 20), (80, 40), (200, 100)];
+
+// entrées des menus, en clés du catalogue de textes
+const MAIN_MENU: [&str; 6] = ["menu.new_game", "menu.load", "menu.high_scores", "menu.achievements", "menu.options", "menu.quit"];
+const PAUSE_MENU: [&str; 3] = ["menu.resume", "menu.save", "menu.quit_to_menu"];
+const OPTION_COUNT: usize = 8;
+// noms proposés par le bouton « personnage au hasard »
+const RANDOM_NAMES: [&str; 8] = ["Aldric", "Brunehilde", "Cassandre", "Dorian", "Elouan", "Faustine", "Gaspard", "Héloïse"];
+
+pub enum AppState {
+    MainMenu,
+    CharacterCreation(Creation),
+    HighScores,
+    Achievements,
+    Options,
+    // capturing : la prochaine touche devient celle de l'action choisie
+    KeyBindings { capturing: bool },
+    Playing,
+    Paused,
+    Inventory,
+    CharacterSheet,
+    // vue d'ensemble des étages visités
+    Dungeon,
+    Help,
+    LevelTransition,
+    Dialog(Dialog),
+    End,
+}
+
+#[derive(Clone, Copy)]
+pub enum DialogAction {
+    Abandon,
+}
+
+// question oui/non posée par-dessus la partie
+pub struct Dialog {
+    pub question: String,
+    pub action: DialogAction,
+    // écran où l'on revient si le joueur refuse
+    pub previous: Box<AppState>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CreationField {
+    Name,
+    Class,
+    Random,
+    Start,
+}
+
+const CREATION_FIELDS: [CreationField; 4] = [CreationField::Name, CreationField::Class, CreationField::Random, CreationField::Start];
+
+// écran de création du personnage : nom saisi au clavier, espèce choisie aux flèches
+pub struct Creation {
+    name: String,
+    espece: Espece,
+    field: CreationField,
+    error: Option<String>,
+}
+
+impl Creation {
+    fn new() -> Self {
+        Creation { name: String::new(), espece: Espece::Homme, field: CreationField::Name, error: None }
+    }
+
+    fn move_field(&mut self, step: usize) {
+        let current = CREATION_FIELDS.iter().position(|&field| field == self.field).unwrap_or(0);
+        self.field = CREATION_FIELDS[(current + step) % CREATION_FIELDS.len()];
+    }
+
+    fn cycle_espece(&mut self, step: usize) {
+        let current = Espece::ALL.iter().position(|&espece| espece == self.espece).unwrap_or(0);
+        self.espece = Espece::ALL[(current + step) % Espece::ALL.len()];
+    }
+}
+
+// réglages choisis dans le menu des options, appliqués aux nouvelles parties
+pub struct Settings {
+    pub combat_mode: CombatMode,
+    pub timing: SpawnTiming,
+    pub tileset: Tileset,
+    pub speed: Speed,
+    pub map_width: usize,
+    pub map_height: usize,
+    pub difficulty: Difficulty,
+    pub bindings: Bindings,
+    pub locale: Locale,
+    // graine imposée en ligne de commande, pour la première partie seulement
+    pub seed: Option<u64>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            combat_mode: CombatMode::Classic,
+            timing: SpawnTiming::RealTime,
+            tileset: Tileset::Emoji,
+            speed: Speed::Normal,
+            map_width: DEFAULT_MAP_WIDTH,
+            map_height: DEFAULT_MAP_HEIGHT,
+            difficulty: Difficulty::Normal,
+            bindings: Bindings::default(),
+            locale: i18n::locale(),
+            seed: None,
+        }
+    }
+}
+
+pub struct App {
+    pub state: AppState,
+    pub game: Option<Game>,
+    pub settings: Settings,
+    // ligne choisie dans le menu affiché
+    selected: usize,
+    // message sous le menu (partie sauvegardée, pas de sauvegarde...)
+    notice: Option<String>,
+    pacer: Pacer,
+    // commandes de la partie en cours (pas de rejeu pour une partie chargée)
+    recording: Option<Replay>,
+    scroll: usize,
+    minimap: bool,
+    last_tick: Instant,
+    // étage connu avant la dernière action, pour repérer un changement d'étage
+    level: u32,
+    end_lines: Vec<String>,
+    // espèce affichée dans le tableau des scores (None = toutes)
+    filter: Option<Espece>,
+    redraw: bool,
+    clear: bool,
+    quit: bool,
+}
+
+// une ligne par entrée, la ligne choisie marquée d'une flèche
+fn menu_lines(items: &[&str], selected: usize) -> Vec<String> {
+    items.iter().enumerate()
+        .map(|(i, item)| format!("{} {}. {}", if i == selected { "▶" } else { " " }, i + 1, t!(item)))
+        .collect()
+}
+
+fn abandon_dialog(previous: AppState) -> AppState {
+    AppState::Dialog(Dialog {
+        question: t!("dialog.abandon"),
+        action: DialogAction::Abandon,
+        previous: Box::new(previous),
+    })
+}
+
+impl App {
+    pub fn new(settings: Settings) -> Self {
+        App {
+            state: AppState::MainMenu,
+            game: None,
+            pacer: Pacer::new(settings.speed, &MessageLog::silent()),
+            settings,
+            recording: None,
+            selected: 0,
+            notice: None,
+            scroll: 0,
+            minimap: false,
+            last_tick: Instant::now(),
+            level: 1,
+            end_lines: Vec::new(),
+            filter: None,
+            redraw: true,
+            clear: true,
+            quit: false,
+        }
+    }
+
+    pub fn start_game(&mut self, name: &str, espece: Espece) -> Result<()> {
+        let seed = self.settings.seed.take().unwrap_or_else(rand::random);
+        let settings = &self.settings;
+        // la partie est construite à partir de l'en-tête du rejeu : le rejouer redonne la même
+        let mut replay = Replay::new(seed, name, espece);
+        replay.combat_mode = settings.combat_mode;
+        replay.timing = settings.timing;
+        replay.difficulty = settings.difficulty;
+        replay.map_width = settings.map_width;
+        replay.map_height = settings.map_height;
+        let game = replay.game()?;
+        self.begin(game);
+        self.recording = Some(replay);
+        Ok(())
+    }
+
+    pub fn load_game_from(&mut self, path: &Path) -> io::Result<()> {
+        let mut game = save::load(path)?;
+        game.log.say(t!("app.loaded"));
+        self.begin(game);
+        Ok(())
+    }
+
+    fn load_game(&mut self) {
+        match save::default_path().and_then(|path| self.load_game_from(&path)) {
+            Ok(()) => {},
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                self.notice = Some(t!("app.no_save"));
+            },
+            Err(error) => self.notice = Some(t!("app.load_failed", error = error)),
+        }
+    }
+
+    fn begin(&mut self, mut game: Game) {
+        match Achievements::load() {
+            Ok(achievements) => game.achievements = achievements,
+            Err(error) => game.log.say(format!("⚠️ {}", t!("app.achievements_read_failed", error = error))),
+        }
+        self.pacer = Pacer::new(self.settings.speed, &game.log);
+        self.recording = None;
+        self.level = game.player.level;
+        self.scroll = 0;
+        self.last_tick = Instant::now();
+        self.game = Some(game);
+        self.state = AppState::Playing;
+        self.clear = true;
+    }
+
+    // Boucle d'événements : dessine, attend une touche ou le prochain tick,
+    // jusqu'à ce que le joueur quitte depuis le menu d'accueil.
+    pub fn run(&mut self, screen: &mut Screen) -> Result<()> {
+        self.redraw = true;
+        self.clear = true;
+        while !self.quit {
+            if self.redraw {
+                self.redraw = false;
+                self.draw(screen)?;
+            }
+
+            // on n'attend jamais indéfiniment le clavier : l'horloge doit pouvoir avancer
+            if !event::poll(POLL_INTERVAL)? {
+                self.idle()?;
+                continue;
+            }
+            match event::read()? {
+                Event::Key(key) => {
+                    self.redraw = true;
+                    self.handle_key(key)?;
+                },
+                Event::Resize(_, _) => {
+                    self.redraw = true;
+                    self.clear = true;
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    fn idle(&mut self) -> Result<()> {
+        let Some(game) = self.game.as_ref() else {
+            return Ok(());
+        };
+        if self.pacer.update(&game.log) {
+            self.redraw = true;
+        }
+        // l'horloge ne tourne que pendant l'exploration, messages affichés
+        let ticking = matches!(self.state, AppState::Playing)
+            && !self.pacer.is_animating(&game.log)
+            && game.clock.timing == SpawnTiming::RealTime;
+        if !ticking {
+            self.last_tick = Instant::now();
+        } else if self.last_tick.elapsed() >= TICK_DURATION {
+            self.last_tick += TICK_DURATION;
+            if self.play(Command::Tick)? {
+                self.redraw = true;
+            }
+        }
+        self.check_progress();
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        let before = mem::discriminant(&self.state);
+        match self.state {
+            AppState::MainMenu => self.main_menu_key(key),
+            AppState::CharacterCreation(_) => self.creation_key(key)?,
+            AppState::HighScores => self.high_scores_key(key),
+            AppState::Achievements => self.state = AppState::MainMenu,
+            AppState::Options => self.options_key(key),
+            AppState::KeyBindings { .. } => self.bindings_key(key),
+            AppState::Playing => self.playing_key(key)?,
+            AppState::Paused => self.paused_key(key),
+            AppState::Inventory | AppState::CharacterSheet | AppState::Dungeon | AppState::Help => self.state = AppState::Playing,
+            AppState::LevelTransition => self.state = AppState::Playing,
+            AppState::Dialog(_) => self.dialog_key(key),
+            // une touche de jeu encore enfoncée ne doit pas faire disparaître le bilan
+            AppState::End if matches!(key.code, KeyCode::Enter | KeyCode::Esc) => {
+                self.game = None;
+                self.selected = 0;
+                self.state = AppState::MainMenu;
+            },
+            AppState::End => {},
+        }
+        if mem::discriminant(&self.state) != before {
+            self.clear = true;
+        }
+        self.check_progress();
+        Ok(())
+    }
+
+    // flèches pour choisir, entrée ou chiffre pour valider ; true si une ligne est validée
+    fn navigate(&mut self, code: KeyCode, count: usize) -> bool {
+        match code {
+            KeyCode::Up => self.selected = (self.selected + count - 1) % count,
+            KeyCode::Down => self.selected = (self.selected + 1) % count,
+            KeyCode::Enter => return true,
+            KeyCode::Char(c) => match c.to_digit(10) {
+                Some(digit) if (1..=count as u32).contains(&digit) => {
+                    self.selected = digit as usize - 1;
+                    return true;
+                },
+                _ => {},
+            },
+            _ => {},
+        }
+        false
+    }
+
+    fn main_menu_key(&mut self, key: KeyEvent) {
+        if !self.navigate(key.code, MAIN_MENU.len()) {
+            return;
+        }
+        self.notice = None;
+        match self.selected {
+            0 => self.state = AppState::CharacterCreation(Creation::new()),
+            1 => self.load_game(),
+            2 => {
+                self.filter = None;
+                self.state = AppState::HighScores;
+            },
+            3 => self.state = AppState::Achievements,
+            4 => {
+                self.selected = 0;
+                self.state = AppState::Options;
+            },
+            _ => self.quit = true,
+        }
+    }
+
+    fn creation_key(&mut self, key: KeyEvent) -> Result<()> {
+        let AppState::CharacterCreation(creation) = &mut self.state else {
+            return Ok(());
+        };
+        let mut start = None;
+        match key.code {
+            KeyCode::Esc => {
+                self.state = AppState::MainMenu;
+                return Ok(());
+            },
+            KeyCode::Tab | KeyCode::Down => creation.move_field(1),
+            KeyCode::BackTab | KeyCode::Up => creation.move_field(CREATION_FIELDS.len() - 1),
+            KeyCode::Left if creation.field == CreationField::Class => creation.cycle_espece(Espece::ALL.len() - 1),
+            KeyCode::Right if creation.field == CreationField::Class => creation.cycle_espece(1),
+            KeyCode::Backspace if creation.field == CreationField::Name => {
+                creation.name.pop();
+            },
+            KeyCode::Char(c) if creation.field == CreationField::Name
+                && !c.is_control()
+                && creation.name.chars().count() < player::MAX_NAME_LENGTH =>
+            {
+                creation.name.push(c);
+                creation.error = None;
+            },
+            KeyCode::Char(c) if creation.field == CreationField::Class => {
+                if let Some(&espece) = c.to_digit(10).and_then(|digit| Espece::ALL.get((digit as usize).wrapping_sub(1))) {
+                    creation.espece = espece;
+                }
+            },
+            KeyCode::Enter => match creation.field {
+                CreationField::Name | CreationField::Class => creation.field = CreationField::Start,
+                CreationField::Random => {
+                    let mut rng = rand::thread_rng();
+                    creation.name = RANDOM_NAMES.choose(&mut rng).map_or_else(|| t!("name.default"), |name| name.to_string());
+                    creation.espece = *Espece::ALL.choose(&mut rng).unwrap_or(&Espece::Homme);
+                    creation.error = None;
+                },
+                CreationField::Start => match player::validate_name(&creation.name) {
+                    Ok(name) => start = Some((name, creation.espece)),
+                    Err(error) => {
+                        creation.error = Some(error);
+                        creation.field = CreationField::Name;
+                    },
+                },
+            },
+            _ => {},
+        }
+        if let Some((name, espece)) = start {
+            self.start_game(&name, espece)?;
+        }
+        Ok(())
+    }
+
+    fn high_scores_key(&mut self, key: KeyEvent) {
+        // Toutes, puis chaque espèce
+        let filters: Vec<Option<Espece>> = std::iter::once(None).chain(Espece::ALL.map(Some)).collect();
+        let current = filters.iter().position(|filter| *filter == self.filter).unwrap_or(0);
+        match key.code {
+            KeyCode::Right => self.filter = filters[(current + 1) % filters.len()],
+            KeyCode::Left => self.filter = filters[(current + filters.len() - 1) % filters.len()],
+            _ => self.state = AppState::MainMenu,
+        }
+    }
+
+    fn options_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('x') => {
+                self.selected = 4;
+                self.state = AppState::MainMenu;
+            },
+            KeyCode::Up => self.selected = (self.selected + OPTION_COUNT - 1) % OPTION_COUNT,
+            KeyCode::Down => self.selected = (self.selected + 1) % OPTION_COUNT,
+            KeyCode::Left | KeyCode::Right | KeyCode::Enter => {
+                let settings = &mut self.settings;
+                match self.selected {
+                    0 => settings.combat_mode = settings.combat_mode.next(),
+                    1 => settings.timing = settings.timing.next(),
+                    2 => settings.tileset = settings.tileset.next(),
+                    3 => settings.speed = settings.speed.next(),
+                    4 => settings.difficulty = settings.difficulty.next(),
+                    6 => {
+                        settings.locale = settings.locale.next();
+                        i18n::set_locale(settings.locale);
+                    },
+                    5 => {
+                        let current = MAP_SIZES.iter().position(|&size| size == (settings.map_width, settings.map_height));
+                        let next = current.map_or(0, |i| (i + 1) % MAP_SIZES.len());
+                        (settings.map_width, settings.map_height) = MAP_SIZES[next];
+                    },
+                    _ => {
+                        self.selected = 0;
+                        self.state = AppState::KeyBindings { capturing: false };
+                    },
+                }
+            },
+            _ => {},
+        }
+    }
+
+    // ligne 0 : disposition ; lignes suivantes : une action chacune
+    fn bindings_key(&mut self, key: KeyEvent) {
+        let AppState::KeyBindings { capturing } = self.state else {
+            return;
+        };
+        let rows = Action::ALL.len() + 1;
+        let bindings = &mut self.settings.bindings;
+        if capturing {
+            match key.code {
+                KeyCode::Char(c) if !c.is_control() => {
+                    bindings.bind(Action::ALL[self.selected - 1], c);
+                    self.notice = None;
+                },
+                KeyCode::Esc => {},
+                _ => {
+                    self.notice = Some(t!("bindings.char_only"));
+                    return;
+                },
+            }
+            self.state = AppState::KeyBindings { capturing: false };
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.notice = bindings.save().err().map(|error| t!("bindings.save_failed", error = error));
+                self.selected = OPTION_COUNT - 1;
+                self.state = AppState::Options;
+            },
+            KeyCode::Up => self.selected = (self.selected + rows - 1) % rows,
+            KeyCode::Down => self.selected = (self.selected + 1) % rows,
+            KeyCode::Left | KeyCode::Right | KeyCode::Enter if self.selected == 0 => {
+                *bindings = Bindings::preset(bindings.preset.next());
+            },
+            KeyCode::Enter => self.state = AppState::KeyBindings { capturing: true },
+            _ => {},
+        }
+    }
+
+    // joue une commande sur la partie et l'ajoute au rejeu ; true si l'affichage a changé
+    fn play(&mut self, command: Command) -> Result<bool> {
+        let Some(game) = self.game.as_mut() else {
+            return Ok(false);
+        };
+        if let Some(replay) = &mut self.recording {
+            replay.record(command);
+        }
+        let changed = command.apply(game)?;
+        // en tour par tour, l'horloge avance d'un tick à chaque action d'exploration
+        if command.is_turn() && game.clock.timing == SpawnTiming::TurnBased {
+            self.play(Command::Tick)?;
+        }
+        Ok(changed)
+    }
+
+    fn playing_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(game) = self.game.as_mut() else {
+            return Ok(());
+        };
+
+        // pendant l'affichage des messages, seule la touche « passer » compte
+        if self.pacer.is_animating(&game.log) {
+            if matches!(key.code, KeyCode::Char(' ') | KeyCode::Enter) {
+                self.pacer.skip(&game.log);
+            }
+            return Ok(());
+        }
+
+        let action = self.settings.bindings.action(key.code);
+        // défilement du journal ; toute autre touche revient aux derniers messages
+        match key.code {
+            KeyCode::PageUp => {
+                self.scroll = (self.scroll + LOG_SCROLL_STEP).min(self.pacer.shown().saturating_sub(1));
+                return Ok(());
+            },
+            KeyCode::PageDown => {
+                self.scroll = self.scroll.saturating_sub(LOG_SCROLL_STEP);
+                return Ok(());
+            },
+            _ if action == Some(Action::Minimap) => {
+                self.minimap = !self.minimap;
+                return Ok(());
+            },
+            _ => self.scroll = 0,
+        }
+
+        let command = match game.state {
+            GameState::Running => match action {
+                Some(action) if action.direction().is_some() => action.direction().map(Command::Move),
+                Some(Action::Aim) => Some(Command::StartTargeting),
+                Some(Action::Inventory) => {
+                    self.state = AppState::Inventory;
+                    None
+                },
+                Some(Action::Speed) => {
+                    self.pacer.speed = self.pacer.speed.next();
+                    self.settings.speed = self.pacer.speed;
+                    game.log.say(t!("speed.changed", speed = self.pacer.speed.name()));
+                    None
+                },
+                Some(Action::Tileset) => {
+                    self.settings.tileset = self.settings.tileset.next();
+                    // la carte change de largeur : on repart d'un écran vide
+                    self.clear = true;
+                    None
+                },
+                _ => {
+                    self.menu_key(key.code, action);
+                    None
+                },
+            },
+            GameState::Targeting => match (key.code, action) {
+                (KeyCode::Tab, _) | (_, Some(Action::MoveRight | Action::MoveDown)) => Some(Command::CycleTarget(1)),
+                (KeyCode::BackTab, _) | (_, Some(Action::MoveLeft | Action::MoveUp)) => Some(Command::CycleTarget(-1)),
+                (KeyCode::Enter, _) | (_, Some(Action::Aim)) => Some(Command::Fire),
+                (KeyCode::Esc, _) => Some(Command::CancelTargeting),
+                _ => None,
+            },
+            GameState::Combat if game.combat_mode == CombatMode::Tactical => match action {
+                Some(action) if action.direction().is_some() => {
+                    action.direction().map(|direction| Command::Tactical(TacticalAction::Move(direction)))
+                },
+                Some(Action::Attack) => Some(Command::Tactical(TacticalAction::Attack)),
+                Some(Action::Special) => Some(Command::Tactical(TacticalAction::SpecialAttack)),
+                Some(Action::Drink) => Some(Command::Tactical(TacticalAction::Drink)),
+                Some(Action::Wait) => Some(Command::Tactical(TacticalAction::Wait)),
+                _ => {
+                    self.menu_key(key.code, action);
+                    None
+                },
+            },
+            GameState::Combat => match action {
+                Some(Action::Attack) => Some(Command::Combat(PlayerCombatAction::Attack)),
+                Some(Action::Special) => Some(Command::Combat(PlayerCombatAction::SpecialAttack)),
+                Some(Action::Drink) => Some(Command::Combat(PlayerCombatAction::Drink)),
+                _ => {
+                    self.menu_key(key.code, action);
+                    None
+                },
+            },
+            GameState::GameOver | GameState::Win => None,
+        };
+
+        if let Some(command) = command {
+            self.play(command)?;
+        }
+        Ok(())
+    }
+
+    // écrans ouverts par-dessus la partie, en exploration comme en combat
+    fn menu_key(&mut self, code: KeyCode, action: Option<Action>) {
+        match action {
+            Some(Action::CharacterSheet) => self.state = AppState::CharacterSheet,
+            Some(Action::Dungeon) => self.state = AppState::Dungeon,
+            Some(Action::Help) => self.state = AppState::Help,
+            Some(Action::Abandon) => self.state = abandon_dialog(AppState::Playing),
+            _ if code == KeyCode::Esc => {
+                self.selected = 0;
+                self.state = AppState::Paused;
+            },
+            _ => {},
+        }
+    }
+
+    fn paused_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Esc {
+            self.notice = None;
+            self.state = AppState::Playing;
+            return;
+        }
+        if !self.navigate(key.code, PAUSE_MENU.len()) {
+            return;
+        }
+        match self.selected {
+            0 => {
+                self.notice = None;
+                self.state = AppState::Playing;
+            },
+            1 => {
+                let Some(game) = self.game.as_mut() else {
+                    return;
+                };
+                let result = save::default_path().and_then(|path| save::save(game, &path).map(|_| path));
+                // la sauvegarde a tiré une nouvelle graine : le rejeu doit en faire autant
+                if let Some(replay) = &mut self.recording {
+                    replay.record(Command::Reseed);
+                }
+                self.notice = Some(match result {
+                    Ok(path) => t!("app.saved", path = path.display()),
+                    Err(error) => t!("app.save_failed", error = error),
+                });
+            },
+            _ => {
+                self.notice = None;
+                self.state = abandon_dialog(AppState::Paused);
+            },
+        }
+    }
+
+    fn dialog_key(&mut self, key: KeyEvent) {
+        let AppState::Dialog(dialog) = mem::replace(&mut self.state, AppState::Playing) else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('o') | KeyCode::Char('O') | KeyCode::Char('y') => self.confirm(dialog.action),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => self.state = *dialog.previous,
+            _ => self.state = AppState::Dialog(dialog),
+        }
+    }
+
+    fn confirm(&mut self, action: DialogAction) {
+        match action {
+            DialogAction::Abandon => {
+                // les succès débloqués restent acquis même si la partie est abandonnée
+                if let Some(game) = self.game.as_mut() {
+                    if let Err(error) = game.achievements.save() {
+                        self.notice = Some(t!("app.achievements_save_failed", error = error));
+                    }
+                }
+                if let Some(Err(error)) = self.recording.take().map(|replay| replay.write()) {
+                    self.notice = Some(t!("app.replay_save_failed", error = error));
+                }
+                self.game = None;
+                self.selected = 0;
+                self.state = AppState::MainMenu;
+            },
+        }
+    }
+
+    // changement d'étage ou fin de partie, une fois les messages affichés
+    fn check_progress(&mut self) {
+        let Some(game) = &self.game else {
+            return;
+        };
+        if !matches!(self.state, AppState::Playing) {
+            return;
+        }
+        let finished = matches!(game.state, GameState::GameOver | GameState::Win);
+        if game.player.level > self.level && !finished {
+            self.level = game.player.level;
+            // l'écran de transition résume déjà la montée de niveau
+            self.pacer.skip(&game.log);
+            self.state = AppState::LevelTransition;
+            self.clear = true;
+            self.redraw = true;
+        } else if finished && !self.pacer.is_animating(&game.log) {
+            self.finish_run();
+        }
+    }
+
+    // score, morgue et succès de la partie terminée, résumés sur l'écran de fin
+    fn finish_run(&mut self) {
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        let mut lines = match game.state {
+            GameState::Win => vec![t!("end.win", level = LAST_LEVEL, score = game.score)],
+            _ => vec![t!("end.lost", outcome = game.outcome(), score = game.score, level = game.player.level)],
+        };
+        lines.push(String::new());
+
+        let entry = HighScore::from_game(game);
+        match highscores::record(&entry).and_then(|_| highscores::load()) {
+            Ok(entries) => {
+                let rank = highscores::rank(&entries, &entry);
+                lines.push(t!("end.rank", rank = rank, total = entries.len()));
+            },
+            Err(error) => lines.push(t!("end.score_save_failed", error = error)),
+        }
+        match morgue::write(game) {
+            Ok(path) => lines.push(t!("end.morgue", path = path.display())),
+            Err(error) => lines.push(t!("end.morgue_failed", error = error)),
+        }
+        if let Err(error) = game.achievements.save() {
+            lines.push(t!("app.achievements_save_failed", error = error));
+        }
+        match self.recording.take().map(|replay| replay.write()) {
+            Some(Ok(path)) => lines.push(t!("end.replay", path = path.display())),
+            Some(Err(error)) => lines.push(t!("app.replay_save_failed", error = error)),
+            None => {},
+        }
+        lines.push(String::new());
+        lines.push(t!("end.press_key"));
+
+        self.end_lines = lines;
+        self.state = AppState::End;
+        self.clear = true;
+        self.redraw = true;
+    }
+
+    fn draw(&mut self, screen: &mut Screen) -> io::Result<()> {
+        if mem::take(&mut self.clear) {
+            screen.clear()?;
+        }
+        match &self.state {
+            AppState::MainMenu => {
+                let mut lines = menu_lines(&MAIN_MENU, self.selected);
+                if let Some(notice) = &self.notice {
+                    lines.push(String::new());
+                    lines.push(notice.clone());
+                }
+                screen.page(&t!("app.title"), &lines, &t!("commands.menu"))
+            },
+            AppState::HighScores => {
+                let filter = self.filter.map_or_else(|| t!("scores.all_especes"), |espece| espece.name());
+                let title = t!("scores.title", filter = filter);
+                let lines = match highscores::load() {
+                    Ok(entries) => highscores::table(&entries, self.filter, HIGH_SCORES_SHOWN),
+                    Err(error) => vec![t!("scores.read_failed", error = error)],
+                };
+                screen.page(&title, &lines, &t!("commands.high_scores"))
+            },
+            AppState::Achievements => {
+                let lines = match Achievements::load() {
+                    Ok(achievements) => achievements.menu_lines(),
+                    Err(error) => vec![t!("app.achievements_read_failed", error = error)],
+                };
+                screen.page(&t!("menu.achievements"), &lines, &t!("commands.back"))
+            },
+            AppState::CharacterCreation(creation) => screen.page(
+                &t!("creation.title"),
+                &creation_lines(creation),
+                &t!("commands.creation"),
+            ),
+            AppState::Options => {
+                let mut lines = self.options_lines();
+                if let Some(notice) = &self.notice {
+                    lines.push(String::new());
+                    lines.push(notice.clone());
+                }
+                screen.page(&t!("menu.options"), &lines, &t!("commands.options"))
+            },
+            AppState::KeyBindings { capturing } => {
+                let commands = if *capturing { t!("commands.capture") } else { t!("commands.bindings") };
+                screen.page(&t!("option.keys"), &self.bindings_lines(*capturing), &commands)
+            },
+            _ => self.draw_game(screen),
+        }
+    }
+
+    fn options_lines(&self) -> Vec<String> {
+        let settings = &self.settings;
+        let values = [
+            (t!("option.combat"), settings.combat_mode.name()),
+            (t!("option.timing"), settings.timing.name()),
+            (t!("option.tileset"), settings.tileset.name()),
+            (t!("option.speed"), settings.speed.name()),
+            (t!("option.difficulty"), settings.difficulty.name()),
+            (t!("option.map_size"), format!("{}x{}", settings.map_width, settings.map_height)),
+            (t!("option.language"), settings.locale.name().to_string()),
+            (t!("option.keys"), settings.bindings.preset.name()),
+        ];
+        values.iter().enumerate()
+            .map(|(i, (label, value))| format!("{} {:<20} ◀ {} ▶", if i == self.selected { "▶" } else { " " }, label, value))
+            .collect()
+    }
+
+    fn bindings_lines(&self, capturing: bool) -> Vec<String> {
+        let bindings = &self.settings.bindings;
+        let marker = |row| if row == self.selected { "▶" } else { " " };
+        let mut lines = vec![format!("{} {:<20} ◀ {} ▶", marker(0), t!("bindings.preset"), bindings.preset.name()), String::new()];
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let key = if capturing && i + 1 == self.selected { "…".to_string() } else { bindings.key_label(*action) };
+            lines.push(format!("{} {:<8} {}", marker(i + 1), key, action.description()));
+        }
+        lines.push(String::new());
+        lines.push(t!("bindings.always"));
+        if let Some(notice) = &self.notice {
+            lines.push(notice.clone());
+        }
+        lines
+    }
+
+    fn draw_game(&self, screen: &mut Screen) -> io::Result<()> {
+        let Some(game) = &self.game else {
+            return Ok(());
+        };
+        let animating = self.pacer.is_animating(&game.log);
+
+        let overlay: Option<(String, Vec<String>)> = match &self.state {
+            AppState::Paused => {
+                let mut lines = menu_lines(&PAUSE_MENU, self.selected);
+                if let Some(notice) = &self.notice {
+                    lines.push(String::new());
+                    lines.push(notice.clone());
+                }
+                Some((t!("overlay.pause"), lines))
+            },
+            AppState::Inventory => Some((t!("overlay.inventory"), game.inventory_lines())),
+            AppState::CharacterSheet => Some((t!("overlay.character"), game.character_lines())),
+            AppState::Dungeon => Some((t!("overlay.dungeon"), game.dungeon_lines())),
+            AppState::Help => Some((t!("overlay.help"), Game::help_lines(&self.settings.bindings))),
+            AppState::LevelTransition => Some((t!("overlay.level"), transition_lines(game))),
+            AppState::Dialog(dialog) => Some((t!("overlay.dialog"), vec![dialog.question.clone(), String::new(), t!("dialog.yes_no")])),
+            AppState::End => Some((t!("overlay.end"), self.end_lines.clone())),
+            _ => None,
+        };
+        let overlay = overlay.map(|(title, lines)| (format!(" {} ", title), lines));
+
+        let commands = match &self.state {
+            AppState::Playing => playing_commands(game, animating, &self.settings.bindings),
+            AppState::Paused => t!("commands.pause"),
+            AppState::Inventory | AppState::CharacterSheet | AppState::Dungeon | AppState::Help => t!("commands.close"),
+            AppState::LevelTransition => t!("commands.continue"),
+            AppState::Dialog(_) => t!("commands.dialog"),
+            AppState::End => t!("commands.end"),
+            _ => String::new(),
+        };
+
+        let view = View {
+            shown: self.pacer.shown(),
+            scroll: self.scroll,
+            overlay: overlay.as_ref().map(|(title, lines)| Overlay { title, lines }),
+            commands: &commands,
+            tileset: self.settings.tileset,
+            minimap: self.minimap,
+        };
+        screen.draw(game, &view)
+    }
+}
+
+fn playing_commands(game: &Game, animating: bool, bindings: &Bindings) -> String {
+    let key = |action| bindings.key_label(action);
+    let moves = format!("{}/{}/{}/{}", key(Action::MoveUp), key(Action::MoveLeft), key(Action::MoveDown), key(Action::MoveRight));
+    match game.state {
+        _ if animating => t!("commands.skip"),
+        GameState::Running => t!(
+            "commands.running",
+            moves = moves, aim = key(Action::Aim), inventory = key(Action::Inventory), sheet = key(Action::CharacterSheet),
+            speed = key(Action::Speed), tileset = key(Action::Tileset), minimap = key(Action::Minimap),
+            dungeon = key(Action::Dungeon), help = key(Action::Help), abandon = key(Action::Abandon),
+        ),
+        GameState::Targeting => t!("commands.targeting", aim = key(Action::Aim)),
+        GameState::Combat if game.combat_mode == CombatMode::Tactical => t!(
+            "commands.tactical",
+            moves = moves, attack = key(Action::Attack), special = key(Action::Special), drink = key(Action::Drink),
+            wait = key(Action::Wait), abandon = key(Action::Abandon),
+        ),
+        GameState::Combat => t!(
+            "commands.combat",
+            attack = key(Action::Attack), special = key(Action::Special), drink = key(Action::Drink),
+            sheet = key(Action::CharacterSheet), abandon = key(Action::Abandon),
+        ),
+        GameState::GameOver | GameState::Win => String::new(),
+    }
+}
+
+// champs du formulaire et aperçu des caractéristiques réelles de l'espèce choisie
+fn creation_lines(creation: &Creation) -> Vec<String> {
+    let marker = |field| if creation.field == field { "▶" } else { " " };
+    let cursor = if creation.field == CreationField::Name { "_" } else { "" };
+    let classes: Vec<String> = Espece::ALL.iter().enumerate()
+        .map(|(i, espece)| {
+            let label = format!("{}. {} {}", i + 1, espece.icon(), espece.name());
+            if *espece == creation.espece { format!("[{}]", label) } else { format!(" {} ", label) }
+        })
+        .collect();
+
+    let mut lines = vec![
+        format!("{} {}: {}{}", marker(CreationField::Name), t!("creation.name"), creation.name, cursor),
+        String::new(),
+        format!("{} {}: {}", marker(CreationField::Class), t!("creation.espece"), classes.join(" ")),
+        format!("  {}", creation.espece.description()),
+        String::new(),
+        format!("── {} ──", t!("creation.preview")),
+    ];
+    let name = if creation.name.trim().is_empty() { t!("name.default") } else { creation.name.trim().to_string() };
+    let preview = Player::new_with_class(&name, creation.espece);
+    lines.extend(preview.sheet_lines().into_iter().map(|line| format!("  {}", line)));
+    lines.push(String::new());
+    lines.push(format!("{} [ {} ]", marker(CreationField::Random), t!("creation.random")));
+    lines.push(format!("{} [ {} ]", marker(CreationField::Start), t!("creation.start")));
+    if let Some(error) = &creation.error {
+        lines.push(String::new());
+        lines.push(format!("❌ {}", error));
+    }
+    lines
+}
+
+fn transition_lines(game: &Game) -> Vec<String> {
+    let player = &game.player;
+    vec![
+        t!("transition.level", level = player.level, last = LAST_LEVEL),
+        String::new(),
+        t!("transition.health", health = player.points_de_vie, max = player.max_health),
+        t!("transition.stats", attack = player.attack, defense = player.defense),
+        t!("transition.potions", potions = player.potions),
+        t!("transition.special"),
+        String::new(),
+        t!("transition.score", score = game.score),
+    ]
+}
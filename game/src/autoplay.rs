@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::combatant::Combatant;
+use crate::error::Result;
+use crate::game::{CombatMode, Game, GameState, PlayerCombatAction};
+use crate::monster::Monster;
+use crate::position::{Direction, Position};
+use crate::tactical::TacticalAction;
+
+// Joueur automatique : il explore jusqu'à la porte, ramasse les 🍗 quand il est blessé,
+// évite les 🔥 quand sa santé est basse et choisit ses actions de combat par règles simples.
+// Sert de démo, de test de bout en bout et de référence pour l'équilibrage.
+
+// en dessous de ce seuil, un 🔥 (50 dégâts) est trop dangereux
+const FIRE_SAFE_HEALTH: i32 = 80;
+
+pub enum AgentAction {
+    Move(Direction),
+    Combat(PlayerCombatAction),
+    Tactical(TacticalAction),
+    Fire,
+}
+
+// Premier pas du plus court chemin vers une case qui vérifie `goal`
+fn first_step(game: &Game, goal: impl Fn(Position) -> bool, avoid_fire: bool, avoid_monsters: bool) -> Option<Direction> {
+    let (width, height) = (game.map.width(), game.map.height());
+    let start = game.player.pos;
+    let mut first: Vec<Vec<Option<Direction>>> = vec![vec![None; width]; height];
+    let mut visited = vec![vec![false; width]; height];
+    let mut queue = VecDeque::new();
+    visited[start.y()][start.x()] = true;
+    queue.push_back(start);
+
+    while let Some(pos) = queue.pop_front() {
+        if pos != start && goal(pos) {
+            return first[pos.y()][pos.x()];
+        }
+        for (direction, next) in pos.neighbours() {
+            if !game.map.is_valid_move(next) || visited[next.y()][next.x()] {
+                continue;
+            }
+            let is_goal = goal(next);
+            if !is_goal && avoid_fire && game.map.is_damage_icon(next) {
+                continue;
+            }
+            if !is_goal && avoid_monsters && game.find_monster_at(next).is_some() {
+                continue;
+            }
+            visited[next.y()][next.x()] = true;
+            first[next.y()][next.x()] = if pos == start { Some(direction) } else { first[pos.y()][pos.x()] };
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+// None : aucun chemin sûr vers la porte
+fn explore(game: &Game) -> Option<AgentAction> {
+    let player = &game.player;
+    let low_health = player.points_de_vie <= FIRE_SAFE_HEALTH;
+    let avoid_monsters = player.points_de_vie * 2 < player.max_health;
+
+    // blessé : d'abord le 🍗 le plus proche
+    if player.points_de_vie < player.max_health - 10 {
+        if let Some(direction) = first_step(game, |pos| game.map.is_health_icon(pos), true, avoid_monsters) {
+            return Some(AgentAction::Move(direction));
+        }
+    }
+
+    let door = |pos| game.map.is_on_door(pos);
+    let step = first_step(game, door, true, avoid_monsters)
+        .or_else(|| first_step(game, door, true, false))
+        .or_else(|| if low_health { None } else { first_step(game, door, false, false) });
+
+    step.map(AgentAction::Move)
+}
+
+fn combat_choice(game: &Game, monster: &Monster) -> PlayerCombatAction {
+    let player = &game.player;
+
+    if player.points_de_vie * 100 < player.max_health * 35 && player.potions > 0 {
+        PlayerCombatAction::Drink
+    } else if player.attaque_speciale && monster.health > player.attack - monster.defense {
+        // on garde l'attaque spéciale pour un monstre qu'une attaque simple n'achèverait pas
+        PlayerCombatAction::SpecialAttack
+    } else {
+        PlayerCombatAction::Attack
+    }
+}
+
+fn tactical_choice(game: &Game, monster: &Monster) -> TacticalAction {
+    let player_pos = game.player.pos;
+    let monster_pos = monster.pos;
+
+    match combat_choice(game, monster) {
+        PlayerCombatAction::Drink => return TacticalAction::Drink,
+        PlayerCombatAction::SpecialAttack => {
+            let range = game.player.special_range();
+            if player_pos.within(monster_pos, range) && game.map.line_of_sight(player_pos, monster_pos) {
+                return TacticalAction::SpecialAttack;
+            }
+        },
+        PlayerCombatAction::Attack => {},
+    }
+    if player_pos.is_adjacent(monster_pos) {
+        return TacticalAction::Attack;
+    }
+
+    let adjacent = |pos: Position| pos.is_adjacent(monster_pos);
+    match first_step(game, adjacent, true, true) {
+        Some(direction) => TacticalAction::Move(direction),
+        None => TacticalAction::Wait,
+    }
+}
+
+// Erreur si le monstre combattu n'est plus dans la liste
+pub fn choose_action(game: &mut Game) -> Result<AgentAction> {
+    let action = match (game.state, game.current_monster_index) {
+        (GameState::Combat, Some(monster_idx)) if game.combat_mode == CombatMode::Tactical => {
+            AgentAction::Tactical(tactical_choice(game, game.monster(monster_idx)?))
+        },
+        (GameState::Combat, Some(monster_idx)) => AgentAction::Combat(combat_choice(game, game.monster(monster_idx)?)),
+        (GameState::Targeting, _) => AgentAction::Fire,
+        _ => explore(game).unwrap_or_else(|| {
+            // pas de chemin sûr : on bouge au hasard en attendant mieux
+            AgentAction::Move(Direction::ALL[game.rng.gen_range(0..Direction::ALL.len())])
+        }),
+    };
+    Ok(action)
+}
+
+// Choisit puis joue une action
+pub fn play_step(game: &mut Game) -> Result<()> {
+    // une action refusée laisse la partie inchangée : l'automate choisira autre chose
+    match choose_action(game)? {
+        AgentAction::Move(direction) => {
+            game.move_player(direction)?;
+        },
+        AgentAction::Combat(action) => {
+            game.combat_turn(action)?;
+        },
+        AgentAction::Tactical(action) => {
+            game.tactical_turn(action)?;
+        },
+        AgentAction::Fire => game.fire_at_target()?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::GameError;
+
+    #[test]
+    fn stale_monster_index_is_an_error() {
+        let mut game = Game::with_seed(2);
+        game.initialize_player("Test", crate::player::Espece::Homme).unwrap();
+        game.state = GameState::Combat;
+        game.current_monster_index = Some(3);
+        for mode in [CombatMode::Classic, CombatMode::Tactical] {
+            game.combat_mode = mode;
+            assert!(matches!(choose_action(&mut game), Err(GameError::NoMonster(3))));
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// Simulateur d'équilibrage : enchaîne des milliers de combats sans affichage ni pause
+// pour chaque espèce face à chaque monstre et niveau, puis affiche les résultats.
+// Avec --runs, il joue des parties entières avec le joueur automatique à la place.
+//
+// cargo run --release --bin simulator -- --fights 5000 --policy smart --csv equilibrage.csv
+// cargo run --release --bin simulator -- --runs 200 --tactical --csv parties.csv
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::process;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use game::autoplay;
+use game::clock::SpawnTiming;
+use game::combat;
+use game::combatant::Combatant;
+use game::error::Result;
+use game::game::{CombatMode, Game, GameState, PlayerCombatAction};
+use game::log::MessageLog;
+use game::monster::{Monster, MonsterSpecies};
+use game::player::{Espece, Player};
+use game::position::Position;
+use game::t;
+
+// au-delà, le combat est compté comme une défaite
+const MAX_TURNS: u32 = 200;
+// au-delà, la partie est arrêtée et comptée comme trop longue
+const MAX_RUN_STEPS: u32 = 5000;
+
+#[derive(Clone, Copy)]
+enum Policy {
+    Attack,
+    Smart,
+    Random,
+}
+
+impl Policy {
+    fn parse(name: &str) -> Option<Policy> {
+        match name {
+            "attack" => Some(Policy::Attack),
+            "smart" => Some(Policy::Smart),
+            "random" => Some(Policy::Random),
+            _ => None,
+        }
+    }
+
+    fn choose(&self, player: &Player, monster: &Monster, rng: &mut StdRng) -> PlayerCombatAction {
+        match self {
+            Policy::Attack => PlayerCombatAction::Attack,
+            Policy::Smart => player.choose_action(monster, rng),
+            Policy::Random => match rng.gen_range(0..3) {
+                0 => PlayerCombatAction::Attack,
+                1 => PlayerCombatAction::SpecialAttack,
+                _ => PlayerCombatAction::Drink,
+            },
+        }
+    }
+}
+
+struct Options {
+    fights: u32,
+    policy: Policy,
+    player_level: u32,
+    csv: Option<String>,
+    seed: Option<u64>,
+    // parties entières par espèce (0 : simulation de combats)
+    runs: u32,
+    tactical: bool,
+}
+
+#[derive(Default)]
+struct Summary {
+    wins: u32,
+    turns: u32,
+    damage_taken: i32,
+    potions: u32,
+}
+
+// le résultat d'une partie entière
+struct RunResult {
+    won: bool,
+    deepest: u32,
+    score: u32,
+    steps: u32,
+    damage_taken: i32,
+    potions: u32,
+    cause: String,
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: simulator [--fights N] [--policy attack|smart|random] [--player-level N] [--csv FICHIER] [--seed N]");
+    eprintln!("       simulator --runs N [--tactical] [--csv FICHIER] [--seed N]");
+    process::exit(2);
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        fights: 1000,
+        policy: Policy::Smart,
+        player_level: 1,
+        csv: None,
+        seed: None,
+        runs: 0,
+        tactical: false,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--fights" => options.fights = value().parse().unwrap_or_else(|_| usage()),
+            "--policy" => options.policy = Policy::parse(&value()).unwrap_or_else(|| usage()),
+            "--player-level" => options.player_level = value().parse().unwrap_or_else(|_| usage()),
+            "--csv" => options.csv = Some(value()),
+            "--seed" => options.seed = Some(value().parse().unwrap_or_else(|_| usage())),
+            "--runs" => options.runs = value().parse().unwrap_or_else(|_| usage()),
+            "--tactical" => options.tactical = true,
+            _ => usage(),
+        }
+    }
+    if options.fights == 0 || options.player_level == 0 {
+        usage();
+    }
+    options
+}
+
+fn simulate_fight(
+    options: &Options,
+    espece: Espece,
+    species: MonsterSpecies,
+    level: u32,
+    rng: &mut StdRng,
+    summary: &mut Summary,
+) {
+    let mut log = MessageLog::silent();
+    let mut player = Player::new_with_class("Simulation", espece);
+    for _ in 1..options.player_level {
+        player.level_up();
+    }
+    let mut monster = Monster::with_species(Position::default(), species, level);
+
+    let mut turns = 0;
+    while player.is_alive() && monster.is_alive() && turns < MAX_TURNS {
+        turns += 1;
+        let action = options.policy.choose(&player, &monster, rng);
+        let mut outcome = combat::exchange(&mut player, &mut monster, action, rng, &mut log);
+        if outcome.is_none() {
+            // attaque spéciale déjà utilisée : on se rabat sur une attaque simple
+            outcome = combat::exchange(&mut player, &mut monster, PlayerCombatAction::Attack, rng, &mut log);
+        }
+        if let Some((player_outcome, monster_outcome)) = outcome {
+            summary.damage_taken += monster_outcome.damage;
+            if player_outcome.potion {
+                summary.potions += 1;
+            }
+        }
+        log.messages.clear();
+    }
+
+    summary.turns += turns;
+    if player.is_alive() && !monster.is_alive() {
+        summary.wins += 1;
+    }
+}
+
+// Une partie complète jouée par l'automate, en tour par tour pour qu'une graine redonne la même partie
+fn simulate_run(options: &Options, espece: Espece, seed: u64) -> Result<RunResult> {
+    let mut game = Game::with_seed(seed);
+    game.initialize_player("Simulation", espece)?;
+    game.log = MessageLog::silent();
+    game.clock.timing = SpawnTiming::TurnBased;
+    if options.tactical {
+        game.combat_mode = CombatMode::Tactical;
+    }
+
+    let mut steps = 0;
+    while steps < MAX_RUN_STEPS && matches!(game.state, GameState::Running | GameState::Combat | GameState::Targeting) {
+        steps += 1;
+        autoplay::play_step(&mut game)?;
+        game.tick()?;
+        game.log.messages.clear();
+    }
+
+    let cause = if steps >= MAX_RUN_STEPS { t!("result.too_long") } else { game.outcome() };
+    Ok(RunResult {
+        won: game.state == GameState::Win,
+        // comme dans les meilleurs scores : chaque nouvel étage fait monter le joueur d'un niveau
+        deepest: game.player.level,
+        score: game.score,
+        steps,
+        damage_taken: game.stats.damage_taken,
+        potions: game.stats.potions,
+        cause,
+    })
+}
+
+fn simulate_runs(options: &Options, base_seed: u64) -> Result<String> {
+    let mut csv = String::from("espece,graine,victoire,etage_max,score,actions,degats_subis,potions,fin\n");
+    let mut causes: BTreeMap<String, u32> = BTreeMap::new();
+
+    println!("{} parties par espèce, combat {}, graines à partir de {}\n",
+        options.runs, if options.tactical { "tactique" } else { "classique" }, base_seed);
+    println!("{:<10} {:>11} {:>10} {:>9} {:>10} {:>11} {:>14} {:>9}",
+        "Espèce", "Victoires %", "Étage moy", "Étage max", "Score moy", "Actions moy", "Dégâts subis", "Potions");
+
+    for espece in Espece::ALL {
+        let mut wins = 0;
+        let (mut floors, mut deepest, mut score, mut steps) = (0, 0, 0, 0);
+        let (mut damage_taken, mut potions) = (0, 0);
+        for i in 0..options.runs {
+            let seed = base_seed.wrapping_add(i as u64);
+            let run = simulate_run(options, espece, seed)?;
+            if run.won {
+                wins += 1;
+            } else {
+                *causes.entry(run.cause.clone()).or_default() += 1;
+            }
+            floors += run.deepest;
+            deepest = deepest.max(run.deepest);
+            score += run.score;
+            steps += run.steps;
+            damage_taken += run.damage_taken;
+            potions += run.potions;
+            csv.push_str(&format!("{},{},{},{},{},{},{},{},{}\n",
+                espece.id(), seed, run.won, run.deepest, run.score, run.steps, run.damage_taken, run.potions, run.cause.replace(',', " ")));
+        }
+
+        let runs = options.runs as f64;
+        println!("{:<10} {:>11.1} {:>10.2} {:>9} {:>10.1} {:>11.1} {:>14.1} {:>9.2}",
+            espece.name(), wins as f64 * 100.0 / runs, floors as f64 / runs, deepest, score as f64 / runs, steps as f64 / runs,
+            damage_taken as f64 / runs, potions as f64 / runs);
+    }
+
+    println!("\nCauses des défaites :");
+    if causes.is_empty() {
+        println!("  aucune");
+    }
+    for (cause, count) in &causes {
+        println!("  {:>5}  {}", count, cause);
+    }
+    Ok(csv)
+}
+
+fn write_csv(path: &str, csv: &str) {
+    let written = File::create(path).and_then(|mut file| file.write_all(csv.as_bytes()));
+    match written {
+        Ok(()) => println!("\nRésultats CSV écrits dans {}", path),
+        Err(error) => {
+            eprintln!("Impossible d'écrire {}: {}", path, error);
+            process::exit(1);
+        },
+    }
+}
+
+fn main() {
+    let options = parse_options();
+    if options.runs > 0 {
+        let csv = simulate_runs(&options, options.seed.unwrap_or_else(rand::random)).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+        if let Some(path) = &options.csv {
+            write_csv(path, &csv);
+        }
+        return;
+    }
+
+    let mut rng = StdRng::seed_from_u64(options.seed.unwrap_or_else(rand::random));
+    let mut csv = String::from("espece,monstre,niveau,combats,victoires_pct,tours_moy,degats_subis_moy,potions_moy\n");
+
+    println!("{} combats par duel, politique {}, joueur niveau {}\n",
+        options.fights,
+        match options.policy {
+            Policy::Attack => "attack",
+            Policy::Smart => "smart",
+            Policy::Random => "random",
+        },
+        options.player_level
+    );
+    println!("{:<10} {:<10} {:>3} {:>11} {:>10} {:>14} {:>9}",
+        "Espèce", "Monstre", "Niv", "Victoires %", "Tours moy", "Dégâts subis", "Potions");
+
+    for espece in Espece::ALL {
+        for species in MonsterSpecies::ALL {
+            for level in 1..=3 {
+                let mut summary = Summary::default();
+                for _ in 0..options.fights {
+                    simulate_fight(&options, espece, species, level, &mut rng, &mut summary);
+                }
+
+                let fights = options.fights as f64;
+                let win_rate = summary.wins as f64 * 100.0 / fights;
+                let avg_turns = summary.turns as f64 / fights;
+                let avg_damage = summary.damage_taken as f64 / fights;
+                let avg_potions = summary.potions as f64 / fights;
+
+                println!("{:<10} {:<10} {:>3} {:>11.1} {:>10.1} {:>14.1} {:>9.2}",
+                    espece.name(), species.name(), level, win_rate, avg_turns, avg_damage, avg_potions);
+                csv.push_str(&format!("{},{},{},{},{:.2},{:.2},{:.2},{:.3}\n",
+                    espece.id(), species.id(), level, options.fights, win_rate, avg_turns, avg_damage, avg_potions));
+            }
+        }
+    }
+
+    if let Some(path) = &options.csv {
+        write_csv(path, &csv);
+    }
+}
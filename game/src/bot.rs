@@ -0,0 +1,272 @@
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::combatant::Combatant;
+use crate::env::{self, Observation};
+use crate::equipment::Ammo;
+use crate::error::Result;
+use crate::game::{CombatMode, Game, GameState, PlayerCombatAction, Turn};
+use crate::position::Direction;
+use crate::t;
+use crate::tactical::TacticalAction;
+
+// Protocole JSON ligne par ligne pour les robots externes (--bot-protocol).
+// Le robot envoie une commande JSON par ligne sur stdin, le jeu répond par un
+// objet JSON par ligne sur stdout : l'état complet après chaque action, ou une erreur.
+//
+//   {"action": "move", "direction": "up"}      (down, left, right, up_left, up_right, down_left, down_right)
+//   {"action": "attack"} / {"action": "special"} / {"action": "drink"} / {"action": "wait"}
+//   {"action": "fire", "target": 2}     (indice dans "monsters")
+//   {"action": "inventory"} / {"action": "quit"}
+//
+// Une action refusée par le moteur donne {"type": "error", "error": "illegal_action", "reason": ...}
+// où "reason" dit pourquoi (blocked, too_far, out_of_range, no_arrows, no_mana...).
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BotCommand {
+    Move { direction: Direction },
+    Attack,
+    Special,
+    Drink,
+    Wait,
+    Fire { target: usize },
+    Inventory,
+    Quit,
+}
+
+#[derive(Serialize)]
+pub struct Inventory {
+    pub name: String,
+    pub espece: &'static str,
+    pub health: i32,
+    pub max_health: i32,
+    pub potions: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub armor: String,
+    pub ranged_weapon: Option<String>,
+    pub arrows: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub accuracy: i32,
+    pub evasion: i32,
+    pub crit_chance: i32,
+    pub block_chance: i32,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    State {
+        #[serde(flatten)]
+        observation: Observation,
+        messages: Vec<String>,
+        legal_actions: Vec<&'static str>,
+        done: bool,
+    },
+    Inventory(Inventory),
+    Error {
+        error: &'static str,
+        message: String,
+        // action refusée par le jeu : code stable de la raison (blocked, too_far, no_arrows...)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<&'static str>,
+    },
+}
+
+fn error(error: &'static str, message: impl Into<String>) -> Response {
+    Response::Error { error, message: message.into(), reason: None }
+}
+
+pub struct BotSession {
+    pub game: Game,
+}
+
+impl BotSession {
+    // la partie doit déjà avoir un joueur et un journal silencieux
+    pub fn new(game: Game) -> Self {
+        BotSession { game }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self.game.state, GameState::GameOver | GameState::Win)
+    }
+
+    pub fn legal_actions(&self) -> Vec<&'static str> {
+        let game = &self.game;
+        let player = &game.player;
+        let mut actions = Vec::new();
+        match game.state {
+            GameState::Running => {
+                actions.push("move");
+                if player.potions > 0 {
+                    actions.push("drink");
+                }
+                if player.ranged_weapon().is_some_and(|weapon| player.has_ammo(&weapon)) {
+                    actions.push("fire");
+                }
+            },
+            GameState::Combat => {
+                if game.combat_mode == CombatMode::Tactical {
+                    actions.push("move");
+                    actions.push("wait");
+                }
+                actions.push("attack");
+                if player.attaque_speciale {
+                    actions.push("special");
+                }
+                if player.potions > 0 {
+                    actions.push("drink");
+                }
+            },
+            GameState::Targeting | GameState::GameOver | GameState::Win => {},
+        }
+        if !self.is_done() {
+            actions.push("inventory");
+            actions.push("quit");
+        }
+        actions
+    }
+
+    pub fn state(&self) -> Response {
+        Response::State {
+            observation: env::observe(&self.game),
+            messages: self.game.log.messages.clone(),
+            legal_actions: self.legal_actions(),
+            done: self.is_done(),
+        }
+    }
+
+    pub fn inventory(&self) -> Inventory {
+        let player = &self.game.player;
+        let weapon = player.ranged_weapon();
+        Inventory {
+            name: player.name.clone(),
+            espece: player.espece.id(),
+            health: player.points_de_vie,
+            max_health: player.max_health,
+            potions: player.potions,
+            attack: player.attack,
+            defense: player.defense,
+            armor: player.armure.name(),
+            ranged_weapon: weapon.map(|weapon| weapon.name()),
+            arrows: if weapon.is_some_and(|weapon| weapon.ammo == Ammo::Arrows) { player.fleches } else { 0 },
+            mana: player.mana,
+            max_mana: player.max_mana,
+            accuracy: player.stats.accuracy,
+            evasion: player.stats.evasion,
+            crit_chance: player.stats.crit_chance,
+            block_chance: player.stats.block_chance,
+        }
+    }
+
+    // Traite une ligne reçue du robot. Renvoie None pour "quit".
+    // Une erreur du moteur arrête la session : la partie n'est plus dans un état sûr.
+    pub fn handle_line(&mut self, line: &str) -> Result<Option<Response>> {
+        let command: BotCommand = match serde_json::from_str(line) {
+            Ok(command) => command,
+            Err(e) => return Ok(Some(error("invalid_json", t!("bot.invalid_json", error = e)))),
+        };
+        match command {
+            BotCommand::Quit => Ok(None),
+            BotCommand::Inventory => Ok(Some(Response::Inventory(self.inventory()))),
+            _ if self.is_done() => Ok(Some(error("game_over", t!("bot.game_over")))),
+            command => self.play(command).map(Some),
+        }
+    }
+
+    fn play(&mut self, command: BotCommand) -> Result<Response> {
+        let game = &mut self.game;
+        game.log.messages.clear();
+
+        let turn = match (game.state, command) {
+            (GameState::Running, BotCommand::Move { direction }) => game.move_player(direction)?,
+            (GameState::Running, BotCommand::Drink) => {
+                match game.player.drink_potion() {
+                    Some(healed) => {
+                        game.stats.potions += 1;
+                        game.log.say(t!("combat.drinks", name = game.player.name, amount = healed));
+                    },
+                    None => return Ok(error("illegal_action", t!("combat.no_potions", name = game.player.name))),
+                }
+                Turn::Played
+            },
+            (GameState::Running, BotCommand::Fire { target }) => match game.start_targeting() {
+                Turn::Played => {
+                    let selected = game.targeting.as_ref().and_then(|targeting| targeting.targets.iter().position(|&i| i == target));
+                    let (Some(selected), Some(targeting)) = (selected, &mut game.targeting) else {
+                        game.cancel_targeting();
+                        return Ok(error("illegal_action", t!("bot.not_a_target", target = target)));
+                    };
+                    targeting.selected = selected;
+                    game.fire_at_target()?;
+                    Turn::Played
+                },
+                refused => refused,
+            },
+            (GameState::Combat, BotCommand::Move { direction }) if game.combat_mode == CombatMode::Tactical => {
+                game.tactical_turn(TacticalAction::Move(direction))?
+            },
+            (GameState::Combat, BotCommand::Wait) if game.combat_mode == CombatMode::Tactical => {
+                game.tactical_turn(TacticalAction::Wait)?
+            },
+            (GameState::Combat, BotCommand::Attack) if game.combat_mode == CombatMode::Tactical => {
+                game.tactical_turn(TacticalAction::Attack)?
+            },
+            (GameState::Combat, BotCommand::Special) if game.combat_mode == CombatMode::Tactical => {
+                game.tactical_turn(TacticalAction::SpecialAttack)?
+            },
+            (GameState::Combat, BotCommand::Drink) if game.combat_mode == CombatMode::Tactical => {
+                game.tactical_turn(TacticalAction::Drink)?
+            },
+            (GameState::Combat, BotCommand::Attack) => game.combat_turn(PlayerCombatAction::Attack)?,
+            (GameState::Combat, BotCommand::Special) => game.combat_turn(PlayerCombatAction::SpecialAttack)?,
+            (GameState::Combat, BotCommand::Drink) => game.combat_turn(PlayerCombatAction::Drink)?,
+            (state, command) => {
+                return Ok(error("illegal_action", t!("bot.wrong_state", action = format!("{:?}", command), state = format!("{:?}", state))));
+            },
+        };
+
+        // le jeu a refusé l'action sans rien jouer : la raison vient du moteur, pas du journal
+        if let Turn::Refused(refusal) = turn {
+            return Ok(Response::Error {
+                error: "illegal_action",
+                message: refusal.message(&game.player.name),
+                reason: Some(refusal.id()),
+            });
+        }
+
+        // une action = un tick d'horloge (apparitions, régénération, dangers)
+        game.tick()?;
+        Ok(self.state())
+    }
+}
+
+fn send(output: &mut impl Write, response: &Response) -> std::io::Result<()> {
+    let line = serde_json::to_string(response).map_err(std::io::Error::other)?;
+    writeln!(output, "{}", line)?;
+    output.flush()
+}
+
+// Boucle principale : état initial, puis une réponse par commande jusqu'à "quit",
+// la fin de l'entrée ou la fin de la partie.
+pub fn run(game: Game, input: impl BufRead, mut output: impl Write) -> Result<()> {
+    let mut session = BotSession::new(game);
+    send(&mut output, &session.state())?;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = session.handle_line(&line)? else {
+            break;
+        };
+        send(&mut output, &response)?;
+        if session.is_done() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+
+use crate::clock::SpawnTiming;
+use crate::game::{CombatMode, Difficulty, MAX_MAP_HEIGHT, MAX_MAP_WIDTH, MIN_MAP_SIZE};
+use crate::i18n::Locale;
+use crate::player::{self, Espece};
+use crate::t;
+use crate::tileset::Tileset;
+
+// Options de la ligne de commande. Une option invalide est une erreur : main affiche
+// le message et l'usage, puis quitte avec un code non nul.
+
+pub fn usage() -> String {
+    t!("cli.usage")
+}
+
+// pause par défaut entre deux actions du joueur automatique ou du rejeu
+const DEFAULT_DELAY: u64 = 300;
+
+#[derive(PartialEq, Debug)]
+pub enum Mode {
+    Play,
+    Replay(PathBuf),
+    Autoplay,
+    BotProtocol,
+}
+
+pub struct Options {
+    pub mode: Mode,
+    pub help: bool,
+    pub name: Option<String>,
+    pub espece: Option<Espece>,
+    pub seed: Option<u64>,
+    pub map_size: Option<(usize, usize)>,
+    pub difficulty: Option<Difficulty>,
+    pub tileset: Option<Tileset>,
+    pub combat_mode: Option<CombatMode>,
+    pub timing: Option<SpawnTiming>,
+    pub load: Option<PathBuf>,
+    pub headless: bool,
+    pub delay: u64,
+    pub locale: Option<Locale>,
+}
+
+fn parse_map_size(value: &str) -> Result<(usize, usize), String> {
+    let error = || t!("cli.map_size", min = MIN_MAP_SIZE, width = MAX_MAP_WIDTH, height = MAX_MAP_HEIGHT, value = value);
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(error)?;
+    let width: usize = width.trim().parse().map_err(|_| error())?;
+    let height: usize = height.trim().parse().map_err(|_| error())?;
+    if !(MIN_MAP_SIZE..=MAX_MAP_WIDTH).contains(&width) || !(MIN_MAP_SIZE..=MAX_MAP_HEIGHT).contains(&height) {
+        return Err(error());
+    }
+    Ok((width, height))
+}
+
+fn parse_combat_mode(value: &str) -> Option<CombatMode> {
+    [CombatMode::Classic, CombatMode::Tactical].into_iter().find(|mode| mode.id() == value.to_lowercase())
+}
+
+pub fn parse(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        mode: Mode::Play,
+        help: false,
+        name: None,
+        espece: None,
+        seed: None,
+        map_size: None,
+        difficulty: None,
+        tileset: None,
+        combat_mode: None,
+        timing: None,
+        load: None,
+        headless: false,
+        delay: DEFAULT_DELAY,
+        locale: None,
+    };
+    let mut modes = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| t!("cli.missing_value", option = arg));
+        match arg.as_str() {
+            "-h" | "--help" => options.help = true,
+            "--name" => options.name = Some(player::validate_name(value()?).map_err(|error| format!("--name: {}", error))?),
+            "--class" => {
+                let value = value()?;
+                options.espece = Some(Espece::parse(value)
+                    .ok_or_else(|| t!("cli.class", value = value))?);
+            },
+            "--seed" => {
+                let value = value()?;
+                options.seed = Some(value.parse().map_err(|_| t!("cli.seed", value = value))?);
+            },
+            "--map-size" => options.map_size = Some(parse_map_size(value()?)?),
+            "--difficulty" => {
+                let value = value()?;
+                options.difficulty = Some(Difficulty::parse(value)
+                    .ok_or_else(|| t!("cli.difficulty", value = value))?);
+            },
+            "--tileset" => {
+                let value = value()?;
+                options.tileset = Some(Tileset::parse(value)
+                    .ok_or_else(|| t!("cli.tileset", value = value))?);
+            },
+            "--combat" => {
+                let value = value()?;
+                options.combat_mode = Some(parse_combat_mode(value)
+                    .ok_or_else(|| t!("cli.combat", value = value))?);
+            },
+            "--tactical" => options.combat_mode = Some(CombatMode::Tactical),
+            "--turn-based" => options.timing = Some(SpawnTiming::TurnBased),
+            "--load" => options.load = Some(PathBuf::from(value()?)),
+            "--replay" => modes.push(Mode::Replay(PathBuf::from(value()?))),
+            "--autoplay" => modes.push(Mode::Autoplay),
+            "--bot-protocol" => modes.push(Mode::BotProtocol),
+            "--headless" => options.headless = true,
+            "--speed" => {
+                let value = value()?;
+                options.delay = value.parse().map_err(|_| t!("cli.speed", value = value))?;
+            },
+            "--lang" => {
+                let value = value()?;
+                options.locale = Some(Locale::parse(value).ok_or_else(|| t!("cli.lang", value = value))?);
+            },
+            _ => return Err(t!("cli.unknown", option = arg)),
+        }
+    }
+
+    if modes.len() > 1 {
+        return Err(t!("cli.modes"));
+    }
+    if let Some(mode) = modes.pop() {
+        options.mode = mode;
+    }
+    if options.load.is_some() && options.mode != Mode::Play {
+        return Err(t!("cli.load_mode"));
+    }
+    // la sauvegarde garde le personnage, la graine et les réglages de la partie ; seul
+    // le jeu de tuiles, qui ne touche que l'affichage, s'applique à la partie rechargée
+    if options.load.is_some()
+        && (options.name.is_some() || options.espece.is_some() || options.seed.is_some() || options.map_size.is_some()
+            || options.difficulty.is_some() || options.combat_mode.is_some() || options.timing.is_some())
+    {
+        return Err(t!("cli.load_game"));
+    }
+    if options.headless && !matches!(options.mode, Mode::Replay(_) | Mode::Autoplay) {
+        return Err(t!("cli.headless"));
+    }
+    if let Mode::Replay(_) = options.mode {
+        if options.name.is_some() || options.espece.is_some() || options.seed.is_some() || options.map_size.is_some()
+            || options.difficulty.is_some() || options.combat_mode.is_some() || options.timing.is_some()
+        {
+            return Err(t!("cli.replay"));
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn error(list: &[&str]) -> String {
+        match parse(&args(list)) {
+            Ok(_) => panic!("{list:?} devrait être refusé"),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn map_size_cases() {
+        let cases: [(&str, Option<(usize, usize)>); 12] = [
+            ("40x20", Some((40, 20))),
+            ("40X20", Some((40, 20))),
+            ("40 x 20", Some((40, 20))),
+            ("5x5", Some((MIN_MAP_SIZE, MIN_MAP_SIZE))),
+            ("200x100", Some((MAX_MAP_WIDTH, MAX_MAP_HEIGHT))),
+            ("4x10", None),
+            ("201x10", None),
+            ("10x101", None),
+            ("10x4", None),
+            ("40", None),
+            ("-1x10", None),
+            ("largexhaute", None),
+        ];
+        for (value, expected) in cases {
+            let error = t!("cli.map_size", min = MIN_MAP_SIZE, width = MAX_MAP_WIDTH, height = MAX_MAP_HEIGHT, value = value);
+            assert_eq!(parse_map_size(value), expected.ok_or(error), "taille {value:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_command_lines_are_refused() {
+        let cases: [(&[&str], String); 18] = [
+            (&["--frobnicate"], t!("cli.unknown", option = "--frobnicate")),
+            (&["--seed"], t!("cli.missing_value", option = "--seed")),
+            (&["--seed", "abc"], t!("cli.seed", value = "abc")),
+            (&["--class", "troll"], t!("cli.class", value = "troll")),
+            (&["--map-size", "300x20"], t!("cli.map_size", min = MIN_MAP_SIZE, width = MAX_MAP_WIDTH, height = MAX_MAP_HEIGHT, value = "300x20")),
+            (&["--autoplay", "--bot-protocol"], t!("cli.modes")),
+            (&["--autoplay", "--replay", "partie.json"], t!("cli.modes")),
+            (&["--load", "save.json", "--seed", "3"], t!("cli.load_game")),
+            (&["--load", "save.json", "--class", "elfe"], t!("cli.load_game")),
+            (&["--load", "save.json", "--tactical"], t!("cli.load_game")),
+            (&["--load", "save.json", "--combat", "classique"], t!("cli.load_game")),
+            (&["--load", "save.json", "--difficulty", "facile"], t!("cli.load_game")),
+            (&["--load", "save.json", "--map-size", "40x20"], t!("cli.load_game")),
+            (&["--load", "save.json", "--turn-based"], t!("cli.load_game")),
+            (&["--load", "save.json", "--autoplay"], t!("cli.load_mode")),
+            (&["--headless"], t!("cli.headless")),
+            (&["--bot-protocol", "--headless"], t!("cli.headless")),
+            (&["--replay", "partie.json", "--seed", "3"], t!("cli.replay")),
+        ];
+        for (list, expected) in cases {
+            assert_eq!(error(list), expected, "arguments {list:?}");
+        }
+    }
+
+    #[test]
+    fn valid_command_lines_are_accepted() {
+        let options = parse(&args(&[])).unwrap();
+        assert_eq!(options.mode, Mode::Play);
+
+        let options = parse(&args(&["--autoplay", "--headless", "--seed", "7", "--map-size", "40x20"])).unwrap();
+        assert_eq!(options.mode, Mode::Autoplay);
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.map_size, Some((40, 20)));
+
+        let options = parse(&args(&["--load", "save.json", "--tileset", "ascii"])).unwrap();
+        assert_eq!(options.load, Some(PathBuf::from("save.json")));
+        assert_eq!(options.tileset, Some(Tileset::Ascii));
+        assert_eq!(options.mode, Mode::Play);
+    }
+}
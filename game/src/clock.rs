@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use crate::t;
+
+// Horloge de jeu : les événements programmés (apparition de monstres, régénération,
+// dangers) sont déclenchés à des ticks fixes, jamais par un minuteur de thread.
+// Une même graine et la même suite de ticks donnent donc toujours la même partie.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimedEvent {
+    SpawnMonster,
+    Regeneration,
+    Hazard,
+}
+
+// Temps réel : un tick par seconde écoulée. Tour par tour : un tick par action du joueur.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SpawnTiming {
+    RealTime,
+    TurnBased,
+}
+
+impl SpawnTiming {
+    pub fn name(&self) -> String {
+        match self {
+            SpawnTiming::RealTime => t!("timing.real_time"),
+            SpawnTiming::TurnBased => t!("timing.turn_based"),
+        }
+    }
+
+    pub fn next(&self) -> SpawnTiming {
+        match self {
+            SpawnTiming::RealTime => SpawnTiming::TurnBased,
+            SpawnTiming::TurnBased => SpawnTiming::RealTime,
+        }
+    }
+}
+
+struct Schedule {
+    event: TimedEvent,
+    every: u64,
+}
+
+// intervalles en ticks
+const SCHEDULES: [Schedule; 3] = [
+    Schedule { event: TimedEvent::SpawnMonster, every: 5 },
+    Schedule { event: TimedEvent::Regeneration, every: 10 },
+    Schedule { event: TimedEvent::Hazard, every: 25 },
+];
+
+pub struct GameClock {
+    pub timing: SpawnTiming,
+    ticks: u64,
+}
+
+impl GameClock {
+    pub fn new(timing: SpawnTiming) -> Self {
+        GameClock { timing, ticks: 0 }
+    }
+
+    // reprend une horloge sauvegardée
+    pub fn resume(timing: SpawnTiming, ticks: u64) -> Self {
+        GameClock { timing, ticks }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    // avance d'un tick et renvoie les événements arrivés à échéance
+    pub fn advance(&mut self) -> Vec<TimedEvent> {
+        self.ticks += 1;
+        SCHEDULES.iter()
+            .filter(|schedule| self.ticks.is_multiple_of(schedule.every))
+            .map(|schedule| schedule.event)
+            .collect()
+    }
+}
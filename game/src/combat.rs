@@ -0,0 +1,117 @@
+use rand::RngCore;
+
+use crate::combatant::Combatant;
+use crate::damage::Roll;
+use crate::game::PlayerCombatAction;
+use crate::log::MessageLog;
+use crate::t;
+
+// Ce qu'une action a produit, pour les statistiques (simulateur, historique)
+#[derive(Default, Clone, Copy)]
+pub struct ActionOutcome {
+    pub played: bool,
+    pub damage: i32,
+    pub healed: i32,
+    pub potion: bool,
+}
+
+// Joue l'action d'un combattant contre un autre.
+// Si l'action n'a pas pu être jouée, `played` vaut false et le tour n'est pas consommé.
+pub fn play_action(
+    actor: &mut dyn Combatant,
+    target: &mut dyn Combatant,
+    action: PlayerCombatAction,
+    rng: &mut dyn RngCore,
+    log: &mut MessageLog,
+) -> ActionOutcome {
+    let mut outcome = ActionOutcome { played: true, ..Default::default() };
+    match action {
+        PlayerCombatAction::Attack => {
+            log.say(t!("combat.prepares_attack", name = actor.name()));
+            let roll = actor.attack(target, rng);
+            outcome.damage = apply_roll(actor, target, roll, log);
+        },
+        PlayerCombatAction::SpecialAttack => {
+            if !actor.special_available() {
+                log.say(t!("combat.special_unavailable"));
+                outcome.played = false;
+                return outcome;
+            }
+            log.say(t!("combat.prepares_special", name = actor.name()));
+            if let Some(message) = actor.special_message() {
+                log.say(message);
+            }
+            let roll = actor.special_attack(target, rng);
+            outcome.damage = apply_roll(actor, target, roll, log);
+        },
+        PlayerCombatAction::Drink => {
+            log.say(t!("combat.takes_potion", name = actor.name()));
+            match actor.drink_potion() {
+                Some(healed) => {
+                    log.say(t!("combat.drinks", name = actor.name(), amount = healed));
+                    log.say(t!("combat.new_health", health = actor.health()));
+                    outcome.healed = healed;
+                    outcome.potion = true;
+                },
+                None => log.say(t!("combat.no_potions", name = actor.name())),
+            }
+        },
+    }
+    outcome
+}
+
+// Applique un jet d'attaque à la cible et renvoie les points de vie qu'elle a vraiment perdus :
+// un coup plus fort que la vie restante ne compte que pour cette vie.
+pub fn apply_roll(
+    actor: &dyn Combatant,
+    target: &mut dyn Combatant,
+    roll: Roll,
+    log: &mut MessageLog,
+) -> i32 {
+    let mut dealt = 0;
+    match roll {
+        Roll::Missed => log.say(t!("combat.missed", name = actor.name())),
+        Roll::Blocked => log.say(t!("combat.blocked", name = target.name())),
+        Roll::Hit { damage, critical } => {
+            if critical {
+                log.say(t!("combat.critical", name = actor.name()));
+            }
+            let health_before = target.health();
+            let result = target.take_damage(damage);
+            log.say(t!("combat.hit", actor = actor.name(), target = target.name(), amount = result.amount, kind = result.kind.name()));
+            if let Some(message) = result.message() {
+                log.say(message);
+            }
+            dealt = health_before - target.health();
+        },
+    }
+
+    log.say(t!("combat.remaining", name = target.name(), health = target.health(), max = target.max_health()));
+    dealt
+}
+
+// Un échange complet : l'action du joueur puis, s'il est encore debout, la riposte du monstre.
+// Renvoie None si l'action du joueur n'a pas pu être jouée.
+pub fn exchange(
+    player: &mut dyn Combatant,
+    monster: &mut dyn Combatant,
+    action: PlayerCombatAction,
+    rng: &mut dyn RngCore,
+    log: &mut MessageLog,
+) -> Option<(ActionOutcome, ActionOutcome)> {
+    log.say(t!("combat.player_turn", name = player.name()));
+
+    let player_outcome = play_action(player, monster, action, rng, log);
+    if !player_outcome.played {
+        return None;
+    }
+
+    let mut monster_outcome = ActionOutcome::default();
+    if monster.is_alive() {
+        log.say(t!("combat.monster_turn"));
+
+        let monster_action = monster.choose_action(player, rng);
+        monster_outcome = play_action(monster, player, monster_action, rng, log);
+    }
+    Some((player_outcome, monster_outcome))
+}
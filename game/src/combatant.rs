@@ -0,0 +1,37 @@
+use rand::RngCore;
+
+use crate::damage::{CombatStats, Damage, DamageResult, Roll};
+use crate::game::PlayerCombatAction;
+
+// Tout ce qui peut se battre : le joueur, les monstres, et plus tard des alliés ou invocations
+pub trait Combatant {
+    fn name(&self) -> String;
+    fn health(&self) -> i32;
+    fn max_health(&self) -> i32;
+    fn defense(&self) -> i32;
+    fn stats(&self) -> &CombatStats;
+
+    fn take_damage(&mut self, damage: Damage) -> DamageResult;
+    // renvoie les points de vie réellement récupérés
+    fn heal(&mut self, amount: i32) -> i32;
+
+    fn attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll;
+    fn special_available(&self) -> bool;
+    fn special_attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll;
+    // portée de l'attaque spéciale en combat tactique (1 = corps à corps)
+    fn special_range(&self) -> i32 {
+        1
+    }
+    // annonce de l'attaque spéciale dans le journal
+    fn special_message(&self) -> Option<String> {
+        None
+    }
+    // renvoie None s'il n'y a plus de potion
+    fn drink_potion(&mut self) -> Option<i32>;
+
+    fn choose_action(&self, opponent: &dyn Combatant, rng: &mut dyn RngCore) -> PlayerCombatAction;
+
+    fn is_alive(&self) -> bool {
+        self.health() > 0
+    }
+}
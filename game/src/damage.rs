@@ -0,0 +1,257 @@
+// Résolution des dégâts partagée entre le joueur et les monstres :
+// précision / esquive, coups critiques, variance, blocage, résistances et faiblesses
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::t;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Frost,
+    Holy,
+    Poison,
+}
+
+impl DamageType {
+    pub fn name(&self) -> String {
+        match self {
+            DamageType::Physical => t!("damage.physical"),
+            DamageType::Fire => t!("damage.fire"),
+            DamageType::Frost => t!("damage.frost"),
+            DamageType::Holy => t!("damage.holy"),
+            DamageType::Poison => t!("damage.poison"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Effectiveness {
+    Immune,
+    Resistant,
+    Normal,
+    Weak,
+}
+
+impl Effectiveness {
+    pub fn apply(&self, amount: i32) -> i32 {
+        match self {
+            Effectiveness::Immune => 0,
+            Effectiveness::Resistant => std::cmp::max(1, amount / 2),
+            Effectiveness::Normal => amount,
+            Effectiveness::Weak => amount * 3 / 2,
+        }
+    }
+
+    // message affiché dans le journal de combat (rien pour un coup normal)
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Effectiveness::Immune => Some(t!("damage.immune")),
+            Effectiveness::Resistant => Some(t!("damage.resistant")),
+            Effectiveness::Normal => None,
+            Effectiveness::Weak => Some(t!("damage.weak")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Damage {
+    pub amount: i32,
+    pub kind: DamageType,
+}
+
+impl Damage {
+    pub fn new(amount: i32, kind: DamageType) -> Self {
+        Damage { amount, kind }
+    }
+
+    pub fn physical(amount: i32) -> Self {
+        Damage::new(amount, DamageType::Physical)
+    }
+}
+
+// Résultat d'un coup une fois les résistances appliquées
+#[derive(Clone, Copy, Debug)]
+pub struct DamageResult {
+    pub amount: i32,
+    pub kind: DamageType,
+    pub effectiveness: Effectiveness,
+}
+
+impl DamageResult {
+    // message d'efficacité pour le journal de combat
+    pub fn message(&self) -> Option<String> {
+        self.effectiveness
+            .message()
+            .map(|message| t!("damage.effectiveness", message = message, kind = self.kind.name()))
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Resistances {
+    pub immune: &'static [DamageType],
+    pub resistant: &'static [DamageType],
+    pub weak: &'static [DamageType],
+}
+
+impl Resistances {
+    pub fn effectiveness(&self, kind: DamageType) -> Effectiveness {
+        if self.immune.contains(&kind) {
+            Effectiveness::Immune
+        } else if self.resistant.contains(&kind) {
+            Effectiveness::Resistant
+        } else if self.weak.contains(&kind) {
+            Effectiveness::Weak
+        } else {
+            Effectiveness::Normal
+        }
+    }
+
+    pub fn apply(&self, damage: Damage) -> DamageResult {
+        let effectiveness = self.effectiveness(damage.kind);
+        DamageResult {
+            amount: effectiveness.apply(damage.amount),
+            kind: damage.kind,
+            effectiveness,
+        }
+    }
+}
+
+// Statistiques de combat (toutes en pourcentage)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CombatStats {
+    pub accuracy: i32,
+    pub evasion: i32,
+    pub crit_chance: i32,
+    pub crit_multiplier: i32,
+    pub block_chance: i32,
+    pub variance: i32,
+}
+
+impl CombatStats {
+    pub fn hit_chance(&self, defender: &CombatStats) -> i32 {
+        (self.accuracy * (100 - defender.evasion) / 100).clamp(5, 100)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Roll {
+    Missed,
+    Blocked,
+    Hit { damage: Damage, critical: bool },
+}
+
+// dégâts de base d'une attaque face à une défense
+pub fn base_damage(power: i32, defense: i32) -> i32 {
+    std::cmp::max(1, power - defense)
+}
+
+// jet d'attaque : touche / esquive, blocage, critique puis variance
+pub fn roll_attack(rng: &mut (impl Rng + ?Sized), attacker: &CombatStats, defender: &CombatStats, damage: Damage) -> Roll {
+    if rng.gen_range(0..100) >= attacker.hit_chance(defender) {
+        return Roll::Missed;
+    }
+    if rng.gen_range(0..100) < defender.block_chance {
+        return Roll::Blocked;
+    }
+
+    let critical = rng.gen_range(0..100) < attacker.crit_chance;
+    let mut amount = damage.amount;
+    if critical {
+        amount = amount * attacker.crit_multiplier / 100;
+    }
+    if attacker.variance > 0 {
+        let spread = rng.gen_range(-attacker.variance..=attacker.variance);
+        amount += amount * spread / 100;
+    }
+
+    Roll::Hit {
+        damage: Damage::new(std::cmp::max(1, amount), damage.kind),
+        critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::player::{Espece, Player};
+
+    const ROLLS: usize = 4000;
+
+    fn stats(accuracy: i32, evasion: i32, crit_chance: i32, block_chance: i32, variance: i32) -> CombatStats {
+        CombatStats { accuracy, evasion, crit_chance, crit_multiplier: 250, block_chance, variance }
+    }
+
+    fn rolls(attacker: &CombatStats, defender: &CombatStats, amount: i32) -> Vec<Roll> {
+        let mut rng = StdRng::seed_from_u64(27);
+        (0..ROLLS).map(|_| roll_attack(&mut rng, attacker, defender, Damage::physical(amount))).collect()
+    }
+
+    fn hits(rolls: &[Roll]) -> Vec<(i32, bool)> {
+        rolls.iter().filter_map(|roll| match roll {
+            Roll::Hit { damage, critical } => Some((damage.amount, *critical)),
+            _ => None,
+        }).collect()
+    }
+
+    #[test]
+    fn hit_chance_is_clamped() {
+        let cases = [(100, 0, 100), (200, 0, 100), (90, 10, 81), (0, 0, 5), (100, 100, 5), (50, 95, 5)];
+        for (accuracy, evasion, expected) in cases {
+            assert_eq!(stats(accuracy, 0, 0, 0, 0).hit_chance(&stats(0, evasion, 0, 0, 0)), expected, "{accuracy}/{evasion}");
+        }
+    }
+
+    #[test]
+    fn perfect_accuracy_always_hits() {
+        let rolls = rolls(&stats(100, 0, 0, 0, 0), &stats(0, 0, 0, 0, 0), 10);
+        assert!(rolls.iter().all(|roll| matches!(roll, Roll::Hit { damage, critical: false } if damage.amount == 10)));
+    }
+
+    #[test]
+    fn hopeless_attacker_only_keeps_the_minimum_chance() {
+        // la chance de toucher ne descend jamais sous 5 % : presque tout est manqué
+        let rolls = rolls(&stats(0, 0, 0, 0, 0), &stats(0, 100, 0, 0, 0), 10);
+        let missed = rolls.iter().filter(|roll| matches!(roll, Roll::Missed)).count();
+        assert!((ROLLS * 92 / 100..=ROLLS * 98 / 100).contains(&missed), "{missed} manqués");
+    }
+
+    #[test]
+    fn critical_applies_the_multiplier() {
+        let hits = hits(&rolls(&stats(100, 0, 100, 0, 0), &stats(0, 0, 0, 0, 0), 10));
+        assert_eq!(hits.len(), ROLLS);
+        assert!(hits.iter().all(|&hit| hit == (25, true)));
+    }
+
+    #[test]
+    fn full_block_stops_every_hit() {
+        let rolls = rolls(&stats(100, 0, 0, 0, 0), &stats(0, 0, 0, 100, 0), 10);
+        assert!(rolls.iter().all(|roll| matches!(roll, Roll::Blocked)));
+    }
+
+    #[test]
+    fn chevalier_blocks_about_a_quarter() {
+        let chevalier = Player::class_stats(Espece::Chevalier);
+        let rolls = rolls(&stats(100, 0, 0, 0, 0), &chevalier, 10);
+        let blocked = rolls.iter().filter(|roll| matches!(roll, Roll::Blocked)).count();
+        // 95 % de coups qui touchent (esquive 5), dont 25 % bloqués
+        assert!((ROLLS * 20 / 100..=ROLLS * 28 / 100).contains(&blocked), "{blocked} bloqués");
+    }
+
+    #[test]
+    fn variance_stays_within_bounds() {
+        let amounts: Vec<i32> = hits(&rolls(&stats(100, 0, 0, 0, 10), &stats(0, 0, 0, 0, 0), 100)).iter().map(|&(amount, _)| amount).collect();
+        assert!(amounts.iter().all(|amount| (90..=110).contains(amount)));
+        assert_eq!(amounts.iter().min(), Some(&90));
+        assert_eq!(amounts.iter().max(), Some(&110));
+
+        // un coup faible reste d'au moins 1
+        let weak = hits(&rolls(&stats(100, 0, 0, 0, 50), &stats(0, 0, 0, 0, 0), 1));
+        assert!(weak.iter().all(|&(amount, _)| amount >= 1));
+        assert_eq!(base_damage(5, 10), 1);
+    }
+}
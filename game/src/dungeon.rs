@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::map::Map;
+use crate::monster::Monster;
+
+// Le donjon : tous les étages générés depuis le début de la partie.
+// L'étage où se trouve le joueur reste dans `Game` (map, monsters) ; les autres attendent
+// ici tels que le joueur les a laissés (monstres tués, 🍗 mangés, cases découvertes).
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Floor {
+    pub map: Map,
+    pub monsters: Vec<Monster>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Dungeon {
+    // indice 0 = étage 1 ; None pour l'étage en cours, qui est dans `Game`
+    floors: Vec<Option<Floor>>,
+    // étage en cours (0 = premier étage)
+    pub depth: usize,
+}
+
+impl Dungeon {
+    // nombre d'étages générés, étage en cours compris
+    pub fn floor_count(&self) -> usize {
+        self.floors.len().max(self.depth + 1)
+    }
+
+    // un étage rangé, None pour l'étage en cours ou un étage pas encore généré
+    pub fn floor(&self, depth: usize) -> Option<&Floor> {
+        self.floors.get(depth).and_then(Option::as_ref)
+    }
+
+    // sort l'étage `depth` du donjon, None s'il n'a pas encore été généré
+    pub fn take(&mut self, depth: usize) -> Option<Floor> {
+        self.floors.get_mut(depth).and_then(Option::take)
+    }
+
+    // range un étage que le joueur quitte
+    pub fn store(&mut self, depth: usize, floor: Floor) {
+        if self.floors.len() <= depth {
+            self.floors.resize_with(depth + 1, || None);
+        }
+        self.floors[depth] = Some(floor);
+    }
+
+    // les étages rangés
+    pub fn stored_floors(&self) -> impl Iterator<Item = &Floor> {
+        self.floors.iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::monster::MonsterSpecies;
+    use crate::position::Position;
+
+    fn floor(seed: u64) -> Floor {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Floor {
+            map: Map::new(10, 10, seed > 0, &mut rng),
+            monsters: vec![Monster::with_species(Position::new(1, 1), MonsterSpecies::Orc, 2)],
+        }
+    }
+
+    #[test]
+    fn new_dungeon_has_only_the_current_floor() {
+        let mut dungeon = Dungeon::default();
+        assert_eq!(dungeon.floor_count(), 1);
+        assert!(dungeon.floor(0).is_none());
+        assert!(dungeon.take(0).is_none());
+        assert!(dungeon.take(3).is_none());
+        assert_eq!(dungeon.stored_floors().count(), 0);
+    }
+
+    #[test]
+    fn store_then_take_gives_the_floor_back_once() {
+        let mut dungeon = Dungeon::default();
+        dungeon.store(0, floor(0));
+        dungeon.depth = 1;
+        assert_eq!(dungeon.floor_count(), 2);
+        assert_eq!(dungeon.floor(0).map(|floor| floor.monsters.len()), Some(1));
+
+        let taken = dungeon.take(0).expect("étage rangé");
+        assert_eq!(taken.monsters[0].pos, Position::new(1, 1));
+        assert!(dungeon.floor(0).is_none());
+        assert!(dungeon.take(0).is_none());
+        // l'étage sorti reste compté : il est devenu l'étage en cours
+        assert_eq!(dungeon.floor_count(), 2);
+    }
+
+    #[test]
+    fn floor_count_follows_depth_and_stored_floors() {
+        let mut dungeon = Dungeon::default();
+        dungeon.store(3, floor(3));
+        assert_eq!(dungeon.floor_count(), 4);
+        assert_eq!(dungeon.stored_floors().count(), 1);
+        assert!(dungeon.floor(1).is_none());
+
+        dungeon.depth = 5;
+        assert_eq!(dungeon.floor_count(), 6);
+    }
+}
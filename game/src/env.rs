@@ -0,0 +1,402 @@
+use serde::Serialize;
+
+use crate::combatant::Combatant;
+use crate::error::Result;
+use crate::game::{CombatMode, Game, GameState, PlayerCombatAction};
+use crate::log::MessageLog;
+use crate::map::Map;
+use crate::player::Espece;
+use crate::position::{Direction, Position};
+use crate::tactical::TacticalAction;
+
+// Environnement d'apprentissage par renforcement adossé à `Game`.
+// Aucune pause ni affichage : tout passe par un journal silencieux, et toute
+// l'aléatoire vient de la graine donnée à `reset`, donc deux épisodes avec
+// la même graine et les mêmes actions sont identiques.
+
+// au-delà, l'épisode est tronqué
+const MAX_STEPS: u32 = 2000;
+
+// Identifiants numériques des cases de la grille observée
+pub const TILE_FLOOR: u8 = 0;
+pub const TILE_WALL: u8 = 1;
+pub const TILE_DOOR: u8 = 2;
+pub const TILE_HEALTH: u8 = 3;
+pub const TILE_FIRE: u8 = 4;
+pub const TILE_PLAYER: u8 = 5;
+pub const TILE_MONSTER: u8 = 6;
+pub const TILE_STAIRS_UP: u8 = 7;
+
+// Actions discrètes : déplacements et actions de combat.
+// Les diagonales viennent après les anciennes actions, qui gardent leur indice.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EnvAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Attack,
+    SpecialAttack,
+    Drink,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl EnvAction {
+    pub const COUNT: usize = 11;
+
+    pub fn from_index(index: usize) -> Option<EnvAction> {
+        match index {
+            0 => Some(EnvAction::Up),
+            1 => Some(EnvAction::Down),
+            2 => Some(EnvAction::Left),
+            3 => Some(EnvAction::Right),
+            4 => Some(EnvAction::Attack),
+            5 => Some(EnvAction::SpecialAttack),
+            6 => Some(EnvAction::Drink),
+            7 => Some(EnvAction::UpLeft),
+            8 => Some(EnvAction::UpRight),
+            9 => Some(EnvAction::DownLeft),
+            10 => Some(EnvAction::DownRight),
+            _ => None,
+        }
+    }
+
+    fn direction(&self) -> Option<Direction> {
+        match self {
+            EnvAction::Up => Some(Direction::Up),
+            EnvAction::Down => Some(Direction::Down),
+            EnvAction::Left => Some(Direction::Left),
+            EnvAction::Right => Some(Direction::Right),
+            EnvAction::UpLeft => Some(Direction::UpLeft),
+            EnvAction::UpRight => Some(Direction::UpRight),
+            EnvAction::DownLeft => Some(Direction::DownLeft),
+            EnvAction::DownRight => Some(Direction::DownRight),
+            _ => None,
+        }
+    }
+
+    fn combat_action(&self) -> Option<PlayerCombatAction> {
+        match self {
+            EnvAction::Attack => Some(PlayerCombatAction::Attack),
+            EnvAction::SpecialAttack => Some(PlayerCombatAction::SpecialAttack),
+            EnvAction::Drink => Some(PlayerCombatAction::Drink),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayerObservation {
+    pub x: usize,
+    pub y: usize,
+    pub health: i32,
+    pub max_health: i32,
+    pub level: u32,
+    pub attack: i32,
+    pub defense: i32,
+    pub potions: i32,
+    pub special_available: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MonsterObservation {
+    pub x: usize,
+    pub y: usize,
+    // indice dans MonsterSpecies::ALL
+    pub species: u8,
+    pub level: u32,
+    pub health: i32,
+    pub max_health: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub engaged: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Observation {
+    // grille [y][x] d'identifiants TILE_*
+    pub tiles: Vec<Vec<u8>>,
+    pub player: PlayerObservation,
+    pub monsters: Vec<MonsterObservation>,
+    pub state: GameState,
+    pub score: u32,
+    // étage en cours (1 = premier) ; le niveau du joueur est l'étage le plus profond atteint
+    pub floor: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StepInfo {
+    // l'action n'avait pas de sens dans l'état courant (ex. attaquer hors combat)
+    pub invalid_action: bool,
+    // épisode arrêté par la limite d'actions
+    pub truncated: bool,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub depth: u32,
+    pub messages: Vec<String>,
+}
+
+// Pondération de la récompense
+const REWARD_SCORE: f64 = 1.0;
+const REWARD_DAMAGE_DEALT: f64 = 0.1;
+const REWARD_DAMAGE_TAKEN: f64 = -0.1;
+const REWARD_DEPTH: f64 = 20.0;
+const REWARD_WIN: f64 = 100.0;
+const REWARD_DEATH: f64 = -100.0;
+const REWARD_INVALID: f64 = -0.5;
+
+pub struct Environment {
+    pub game: Game,
+    espece: Espece,
+    combat_mode: CombatMode,
+    steps: u32,
+}
+
+fn tile_id(map: &Map, pos: Position) -> u8 {
+    match map.tile(pos) {
+        '⬜' => TILE_WALL,
+        '🚪' => TILE_DOOR,
+        '🔼' => TILE_STAIRS_UP,
+        '🍗' => TILE_HEALTH,
+        '🔥' => TILE_FIRE,
+        '👾' => TILE_MONSTER,
+        '⬛' => TILE_FLOOR,
+        _ => TILE_PLAYER,
+    }
+}
+
+pub fn observe(game: &Game) -> Observation {
+    let map = &game.map;
+    let tiles = (0..map.height())
+        .map(|y| (0..map.width()).map(|x| tile_id(map, Position::new(x, y))).collect())
+        .collect();
+
+    let player = &game.player;
+    let monsters = game.monsters.iter().enumerate()
+        .map(|(i, monster)| MonsterObservation {
+            x: monster.pos.x(),
+            y: monster.pos.y(),
+            species: monster.species as u8,
+            level: monster.level,
+            health: monster.health,
+            max_health: monster.max_health,
+            attack: monster.attack,
+            defense: monster.defense,
+            engaged: game.current_monster_index == Some(i),
+        })
+        .collect();
+
+    Observation {
+        tiles,
+        player: PlayerObservation {
+            x: player.pos.x(),
+            y: player.pos.y(),
+            health: player.points_de_vie,
+            max_health: player.max_health,
+            level: player.level,
+            attack: player.attack,
+            defense: player.defense,
+            potions: player.potions,
+            special_available: player.attaque_speciale,
+        },
+        monsters,
+        state: game.state,
+        score: game.score,
+        floor: game.floor(),
+    }
+}
+
+impl Environment {
+    pub fn new(espece: Espece, combat_mode: CombatMode) -> Result<Self> {
+        let mut env = Environment {
+            game: Game::with_seed(0),
+            espece,
+            combat_mode,
+            steps: 0,
+        };
+        env.reset(0)?;
+        Ok(env)
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Result<Observation> {
+        self.game = Game::with_seed(seed);
+        self.game.log = MessageLog::silent();
+        self.game.combat_mode = self.combat_mode;
+        self.game.initialize_player("Agent", self.espece)?;
+        self.steps = 0;
+        Ok(self.observe())
+    }
+
+    pub fn observe(&self) -> Observation {
+        observe(&self.game)
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(self.game.state, GameState::GameOver | GameState::Win)
+    }
+
+    pub fn step(&mut self, action: EnvAction) -> Result<(Observation, f64, bool, StepInfo)> {
+        let mut info = StepInfo::default();
+        if self.is_done() {
+            info.invalid_action = true;
+            return Ok((self.observe(), 0.0, true, info));
+        }
+
+        let score_before = self.game.score;
+        let level_before = self.game.player.level;
+        let health_before = self.game.player.points_de_vie;
+        let dealt_before = self.game.stats.damage_dealt;
+        self.game.log.messages.clear();
+
+        match (self.game.state, action.direction(), action.combat_action()) {
+            (GameState::Running, Some(direction), _) => {
+                self.game.move_player(direction)?;
+            },
+            (GameState::Combat, Some(direction), _) if self.combat_mode == CombatMode::Tactical => {
+                self.game.tactical_turn(TacticalAction::Move(direction))?;
+            },
+            (GameState::Combat, _, Some(combat_action)) if self.combat_mode == CombatMode::Tactical => {
+                let tactical_action = match combat_action {
+                    PlayerCombatAction::Attack => TacticalAction::Attack,
+                    PlayerCombatAction::SpecialAttack => TacticalAction::SpecialAttack,
+                    PlayerCombatAction::Drink => TacticalAction::Drink,
+                };
+                self.game.tactical_turn(tactical_action)?;
+            },
+            (GameState::Combat, _, Some(combat_action)) => {
+                self.game.combat_turn(combat_action)?;
+            },
+            _ => info.invalid_action = true,
+        }
+
+        // une action = un tick d'horloge (apparitions, régénération, dangers)
+        self.steps += 1;
+        self.game.tick()?;
+
+        // les dégâts que le combat a vraiment infligés : fuir ou s'échapper ne rapporte rien
+        info.damage_dealt = self.game.stats.damage_dealt - dealt_before;
+        info.damage_taken = std::cmp::max(0, health_before - self.game.player.health());
+        info.depth = self.game.player.level;
+        info.messages = self.game.log.messages.clone();
+
+        let mut reward = REWARD_SCORE * (self.game.score - score_before) as f64
+            + REWARD_DAMAGE_DEALT * info.damage_dealt as f64
+            + REWARD_DAMAGE_TAKEN * info.damage_taken as f64
+            + REWARD_DEPTH * (self.game.player.level - level_before) as f64;
+        if info.invalid_action {
+            reward += REWARD_INVALID;
+        }
+        match self.game.state {
+            GameState::Win => reward += REWARD_WIN,
+            GameState::GameOver => reward += REWARD_DEATH,
+            _ => {},
+        }
+
+        let mut done = self.is_done();
+        if !done && self.steps >= MAX_STEPS {
+            info.truncated = true;
+            done = true;
+        }
+        Ok((self.observe(), reward, done, info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monster::{Monster, MonsterSpecies};
+
+    fn env_action(direction: Direction) -> EnvAction {
+        match direction {
+            Direction::Up => EnvAction::Up,
+            Direction::Down => EnvAction::Down,
+            Direction::Left => EnvAction::Left,
+            Direction::Right => EnvAction::Right,
+            Direction::UpLeft => EnvAction::UpLeft,
+            Direction::UpRight => EnvAction::UpRight,
+            Direction::DownLeft => EnvAction::DownLeft,
+            Direction::DownRight => EnvAction::DownRight,
+        }
+    }
+
+    // une case libre voisine de `pos`, avec la direction pour y aller
+    fn free_neighbour(game: &Game, pos: Position) -> (Direction, Position) {
+        pos.neighbours().find(|&(_, next)| game.map.is_empty(next)).expect("case libre")
+    }
+
+    // combat tactique contre un gobelin posé à côté du joueur
+    fn engage_goblin(env: &mut Environment, health: i32) -> usize {
+        let game = &mut env.game;
+        let (_, pos) = free_neighbour(game, game.player.pos);
+        let mut monster = Monster::with_species(pos, MonsterSpecies::Goblin, 1);
+        monster.health = health;
+        game.map.place_monster(pos).unwrap();
+        game.monsters.push(monster);
+        let idx = game.monsters.len() - 1;
+        game.start_tactical_combat(idx).unwrap();
+        idx
+    }
+
+    fn run(seed: u64) -> Vec<(String, f64, bool)> {
+        let mut env = Environment::new(Espece::Chevalier, CombatMode::Tactical).unwrap();
+        env.reset(seed).unwrap();
+        (0..300)
+            .map(|i| {
+                let (observation, reward, done, _) = env.step(EnvAction::from_index(i * 7 % EnvAction::COUNT).unwrap()).unwrap();
+                (serde_json::to_string(&observation).unwrap(), reward, done)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_episode() {
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn invalid_action_is_penalised() {
+        let mut env = Environment::new(Espece::Chevalier, CombatMode::Classic).unwrap();
+        let (_, reward, done, info) = env.step(EnvAction::Attack).unwrap();
+        assert!(info.invalid_action);
+        assert!(!done);
+        assert_eq!(info.damage_dealt, 0);
+        assert_eq!(reward, REWARD_INVALID);
+    }
+
+    #[test]
+    fn kill_rewards_damage_and_score() {
+        let mut env = Environment::new(Espece::Chevalier, CombatMode::Tactical).unwrap();
+        engage_goblin(&mut env, 1);
+        for _ in 0..50 {
+            let (_, reward, _, info) = env.step(EnvAction::Attack).unwrap();
+            if env.game.monsters.is_empty() {
+                assert_eq!(info.damage_dealt, 1);
+                assert!(reward >= REWARD_SCORE * 10.0 + REWARD_DAMAGE_DEALT);
+                return;
+            }
+            assert_eq!(info.damage_dealt, 0);
+        }
+        panic!("le gobelin aurait dû mourir");
+    }
+
+    #[test]
+    fn fleeing_deals_no_damage() {
+        let mut env = Environment::new(Espece::Chevalier, CombatMode::Tactical).unwrap();
+        // le joueur se place à côté de la porte avant d'engager le combat
+        let door = env.game.map.door();
+        let (_, start) = free_neighbour(&env.game, door);
+        env.game.enter_tile(start).unwrap();
+        let idx = engage_goblin(&mut env, 40);
+        let direction = start.neighbours().find(|&(_, next)| next == door).unwrap().0;
+
+        let (observation, _, _, info) = env.step(env_action(direction)).unwrap();
+        assert!(!info.invalid_action);
+        assert_eq!(info.damage_dealt, 0);
+        assert_eq!(observation.floor, 2);
+        assert_eq!(env.game.dungeon.floor(0).unwrap().monsters[idx].health, 40);
+    }
+}
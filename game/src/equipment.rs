@@ -0,0 +1,93 @@
+use crate::damage::{DamageType, Resistances};
+use crate::player::Espece;
+use crate::t;
+
+pub struct Equipment {
+    // clé du nom dans le catalogue de textes
+    pub name_key: &'static str,
+    pub resistances: Resistances,
+}
+
+impl Equipment {
+    pub fn name(&self) -> String {
+        t!(self.name_key)
+    }
+
+    // armure de départ de chaque espèce
+    pub fn starting_armor(espece: Espece) -> Self {
+        match espece {
+            Espece::Homme => Equipment {
+                name_key: "armor.leather",
+                resistances: Resistances {
+                    immune: &[],
+                    resistant: &[DamageType::Poison],
+                    weak: &[DamageType::Fire],
+                },
+            },
+            Espece::Sorciere => Equipment {
+                name_key: "armor.robe",
+                resistances: Resistances {
+                    immune: &[],
+                    resistant: &[DamageType::Fire, DamageType::Frost],
+                    weak: &[DamageType::Physical],
+                },
+            },
+            Espece::Elfe => Equipment {
+                name_key: "armor.cloak",
+                resistances: Resistances {
+                    immune: &[DamageType::Poison],
+                    resistant: &[],
+                    weak: &[DamageType::Frost],
+                },
+            },
+            Espece::Chevalier => Equipment {
+                name_key: "armor.blessed",
+                resistances: Resistances {
+                    immune: &[],
+                    resistant: &[DamageType::Physical, DamageType::Holy],
+                    weak: &[DamageType::Frost],
+                },
+            },
+        }
+    }
+}
+
+// Ce que consomme une arme à distance à chaque tir
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Ammo {
+    Arrows,
+    Mana(i32),
+}
+
+#[derive(Clone, Copy)]
+pub struct RangedWeapon {
+    pub name_key: &'static str,
+    pub range: i32,
+    pub kind: DamageType,
+    pub ammo: Ammo,
+}
+
+impl RangedWeapon {
+    pub fn name(&self) -> String {
+        t!(self.name_key)
+    }
+
+    // seules l'Elfe (arc) et la Sorcière (sort) peuvent attaquer à distance hors combat
+    pub fn for_class(espece: Espece) -> Option<Self> {
+        match espece {
+            Espece::Elfe => Some(RangedWeapon {
+                name_key: "weapon.bow",
+                range: 6,
+                kind: DamageType::Physical,
+                ammo: Ammo::Arrows,
+            }),
+            Espece::Sorciere => Some(RangedWeapon {
+                name_key: "weapon.frost_bolt",
+                range: 5,
+                kind: DamageType::Frost,
+                ammo: Ammo::Mana(10),
+            }),
+            _ => None,
+        }
+    }
+}
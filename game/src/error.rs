@@ -0,0 +1,44 @@
+use std::fmt;
+use std::io;
+
+use crate::position::Position;
+use crate::t;
+
+// Erreurs du jeu. Le moteur ne panique plus sur une case hors de la carte ou un monstre
+// disparu : l'erreur remonte jusqu'à main, qui rend le terminal avant de l'afficher.
+
+#[derive(Debug)]
+pub enum GameError {
+    Io(io::Error),
+    // case en dehors de la carte
+    OutOfMap(Position),
+    // index d'un monstre qui n'est plus dans la liste
+    NoMonster(usize),
+}
+
+pub type Result<T> = std::result::Result<T, GameError>;
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Io(error) => write!(f, "{}", t!("error.io", error = error)),
+            GameError::OutOfMap(pos) => write!(f, "{}", t!("error.out_of_map", x = pos.x(), y = pos.y())),
+            GameError::NoMonster(index) => write!(f, "{}", t!("error.no_monster", index = index)),
+        }
+    }
+}
+
+impl std::error::Error for GameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GameError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(error: io::Error) -> Self {
+        GameError::Io(error)
+    }
+}
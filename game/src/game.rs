@@ -1,30 +1,208 @@
+use crate::achievements::{Achievements, GameEvent};
+use crate::clock::{GameClock, SpawnTiming, TimedEvent};
+use crate::combat;
+use crate::combatant::Combatant;
+use crate::damage::{Damage, DamageType};
+use crate::dungeon::{Dungeon, Floor};
+use crate::error::{GameError, Result};
+use crate::log::MessageLog;
+use crate::equipment::Ammo;
+use crate::keys::{Action, Bindings};
 use crate::map::Map;
 use crate::player::{Player, Espece};
-use crate::monster::{Monster, MonsterSpecies};
-use rand::Rng;
+use crate::position::{Direction, Position};
+use crate::stats::RunStats;
+use crate::t;
+use crate::monster::Monster;
+use crate::targeting::Targeting;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use rand::SeedableRng;
 
-#[derive(PartialEq, Clone, Copy)] 
+// Dimensions des cartes générées
+pub const DEFAULT_MAP_WIDTH: usize = 10;
+pub const DEFAULT_MAP_HEIGHT: usize = 10;
+pub const MIN_MAP_SIZE: usize = 5;
+pub const MAX_MAP_WIDTH: usize = 200;
+pub const MAX_MAP_HEIGHT: usize = 100;
+// atteindre cet étage gagne la partie
+pub const LAST_LEVEL: u32 = 5;
+
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum GameState {
     Running,
     Combat,
+    Targeting,
     GameOver,
     Win,
 }
 
-#[derive(PartialEq)]
+// Classique : écran de combat à part. Tactique : le combat se joue sur la carte.
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum CombatMode {
+    Classic,
+    Tactical,
+}
+
+impl CombatMode {
+    // nom accepté en ligne de commande
+    pub fn id(&self) -> &'static str {
+        match self {
+            CombatMode::Classic => "classique",
+            CombatMode::Tactical => "tactique",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            CombatMode::Classic => t!("mode.classic"),
+            CombatMode::Tactical => t!("mode.tactical"),
+        }
+    }
+
+    pub fn next(&self) -> CombatMode {
+        match self {
+            CombatMode::Classic => CombatMode::Tactical,
+            CombatMode::Tactical => CombatMode::Classic,
+        }
+    }
+}
+
+// Les monstres qui apparaissent ont plus ou moins de points de vie et d'attaque
+#[derive(PartialEq, Clone, Copy, Default, Debug, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    // nom accepté en ligne de commande
+    pub fn id(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "facile",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "difficile",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Difficulty::Easy => t!("difficulty.easy"),
+            Difficulty::Normal => t!("difficulty.normal"),
+            Difficulty::Hard => t!("difficulty.hard"),
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL.into_iter().find(|difficulty| difficulty.id() == name.to_lowercase())
+    }
+
+    pub fn next(&self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    // en pourcentage des valeurs normales
+    pub fn monster_percent(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 75,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 130,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize, Debug)]
 pub enum PlayerCombatAction {
     Attack,
     SpecialAttack,
     Drink,
 }
 
+// Raison pour laquelle le jeu refuse une action : rien n'est joué et le tour n'est pas consommé
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Refusal {
+    // l'action ne se joue pas dans l'état en cours (exploration, combat, visée)
+    WrongState,
+    Blocked,
+    TooFar,
+    OutOfRange(i32),
+    SpecialUnavailable,
+    NoWeapon,
+    NoArrows,
+    NoMana(i32),
+    NoTarget(i32),
+}
+
+impl Refusal {
+    // code stable pour les robots, qui ne dépend pas de la langue
+    pub fn id(&self) -> &'static str {
+        match self {
+            Refusal::WrongState => "wrong_state",
+            Refusal::Blocked => "blocked",
+            Refusal::TooFar => "too_far",
+            Refusal::OutOfRange(_) => "out_of_range",
+            Refusal::SpecialUnavailable => "special_unavailable",
+            Refusal::NoWeapon => "no_weapon",
+            Refusal::NoArrows => "no_arrows",
+            Refusal::NoMana(_) => "no_mana",
+            Refusal::NoTarget(_) => "no_target",
+        }
+    }
+
+    pub fn message(&self, name: &str) -> String {
+        match self {
+            Refusal::WrongState => t!("refusal.wrong_state"),
+            Refusal::Blocked => t!("tactical.blocked"),
+            Refusal::TooFar => t!("tactical.too_far"),
+            Refusal::OutOfRange(range) => t!("tactical.out_of_range", range = range),
+            Refusal::SpecialUnavailable => t!("combat.special_unavailable"),
+            Refusal::NoWeapon => t!("targeting.no_weapon", name = name),
+            Refusal::NoArrows => t!("targeting.no_arrows"),
+            Refusal::NoMana(cost) => t!("targeting.no_mana", cost = cost),
+            Refusal::NoTarget(range) => t!("targeting.no_target", range = range),
+        }
+    }
+}
+
+// ce qu'est devenue une action du joueur
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Turn {
+    Played,
+    Refused(Refusal),
+}
+
 pub struct Game {
+    // étage en cours ; les autres étages visités sont dans `dungeon`
     pub map: Map,
     pub player: Player,
     pub monsters: Vec<Monster>,
+    pub dungeon: Dungeon,
     pub state: GameState,
     pub score: u32,
     pub current_monster_index: Option<usize>,
+    pub combat_mode: CombatMode,
+    pub difficulty: Difficulty,
+    pub targeting: Option<Targeting>,
+    pub log: MessageLog,
+    // toute l'aléatoire de la partie vient de ce générateur : même graine, même partie
+    pub seed: u64,
+    pub rng: StdRng,
+    pub clock: GameClock,
+    // taille de chaque nouvelle carte
+    pub map_width: usize,
+    pub map_height: usize,
+    // ce qui a tué le joueur, pour le tableau des scores
+    pub death_cause: Option<String>,
+    pub stats: RunStats,
+    pub achievements: Achievements,
 }
 
 impl Default for Game {
@@ -35,369 +213,477 @@ impl Default for Game {
 
 impl Game {
     pub fn new() -> Self {
+        Game::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Game::with_map_size(seed, DEFAULT_MAP_WIDTH, DEFAULT_MAP_HEIGHT)
+    }
+
+    // la taille est ramenée entre MIN_MAP_SIZE et MAX_MAP_WIDTH x MAX_MAP_HEIGHT
+    pub fn with_map_size(seed: u64, width: usize, height: usize) -> Self {
+        let map_width = width.clamp(MIN_MAP_SIZE, MAX_MAP_WIDTH);
+        let map_height = height.clamp(MIN_MAP_SIZE, MAX_MAP_HEIGHT);
+        let mut rng = StdRng::seed_from_u64(seed);
         Game {
-            map: Map::new(10, 10),
+            map: Map::new(map_width, map_height, false, &mut rng),
             player: Player::new("Default"),
             monsters: Vec::new(),
+            dungeon: Dungeon::default(),
             state: GameState::Running,
             score: 0,
             current_monster_index: None,
+            combat_mode: CombatMode::Classic,
+            difficulty: Difficulty::Normal,
+            targeting: None,
+            log: MessageLog::silent(),
+            seed,
+            rng,
+            clock: GameClock::new(SpawnTiming::RealTime),
+            map_width,
+            map_height,
+            death_cause: None,
+            stats: RunStats::default(),
+            achievements: Achievements::default(),
         }
     }
 
-    pub fn initialize_player(&mut self, name: &str, espece: Espece) {
+    pub fn initialize_player(&mut self, name: &str, espece: Espece) -> Result<()> {
         self.player = Player::new_with_class(name, espece);
-        self.map.place_player(0, 0, &self.player);
+        self.player.pos = self.map.entrance();
+        self.map.place_player(self.player.pos, &self.player)
     }
 
-    pub fn spawn_random_monster(&mut self) {
-        let mut _rng = rand::thread_rng();
+    pub fn spawn_random_monster(&mut self) -> Result<()> {
         if self.monsters.len() < 10 {  // Limit number of monsters
-            let (x, y) = self.map.get_random_empty_position();
-            let  monster = Monster::new(x, y);
-            self.map.place_monster(x, y);
+            let Some(pos) = self.map.get_random_empty_position(&mut self.rng) else {
+                return Ok(());
+            };
+            let mut monster = Monster::new(pos, &mut self.rng);
+            monster.scale(self.difficulty.monster_percent());
+            self.map.place_monster(pos)?;
             self.monsters.push(monster);
         }
+        Ok(())
     }
 
-    pub fn find_monster_at(&self, x: usize, y: usize) -> Option<usize> {
-        self.monsters.iter().position(|m| m.x == x && m.y == y)
+    // monstre désigné par un index, erreur s'il n'est plus dans la liste
+    pub fn monster(&self, monster_idx: usize) -> Result<&Monster> {
+        self.monsters.get(monster_idx).ok_or(GameError::NoMonster(monster_idx))
     }
 
-    pub fn move_player(&mut self, dx: i32, dy: i32) {
-        if self.state == GameState::Combat {
-            return;
+    // Avance l'horloge d'un tick et applique les événements programmés.
+    // L'horloge est arrêtée hors exploration (combat, visée, fin de partie).
+    // Renvoie true si quelque chose a changé à l'écran.
+    pub fn tick(&mut self) -> Result<bool> {
+        if self.state != GameState::Running {
+            return Ok(false);
         }
-    
-        let new_x = (self.player.x as i32 + dx) as usize;
-        let new_y = (self.player.y as i32 + dy) as usize;
-    
-        if self.state == GameState::Running && self.map.is_valid_move(new_x, new_y) {
-            self.map.clear_position(self.player.x, self.player.y);
-            self.player.x = new_x;
-            self.player.y = new_y;
-            self.map.place_player(new_x, new_y, &self.player);
-    
-            // Check for special icons
-            if self.map.is_health_icon(new_x, new_y) {
-                self.player.points_de_vie = std::cmp::min(
-                    self.player.points_de_vie + 10, 
-                    self.player.max_health
-                );
-                self.map.clear_special_icon(new_x, new_y);
-                println!("❤️ Vous récupérez 10 points de vie!");
+        let events = self.clock.advance();
+        for event in &events {
+            match event {
+                TimedEvent::SpawnMonster => self.spawn_random_monster()?,
+                TimedEvent::Regeneration => {
+                    self.player.heal(1);
+                    self.player.regen_mana(2);
+                },
+                TimedEvent::Hazard => {
+                    if let Some(pos) = self.map.get_random_empty_position(&mut self.rng) {
+                        self.map.place_hazard(pos)?;
+                        self.log.say(t!("game.fire_started"));
+                    }
+                },
             }
+        }
+        Ok(!events.is_empty())
+    }
+
+    pub fn find_monster_at(&self, pos: Position) -> Option<usize> {
+        self.monsters.iter().position(|m| m.pos == pos)
+    }
+
+    // Buter contre un mur ou le bord de la carte n'écrit rien dans le journal
+    pub fn move_player(&mut self, direction: Direction) -> Result<Turn> {
+        if self.state != GameState::Running {
+            return Ok(Turn::Refused(Refusal::WrongState));
+        }
     
-            if self.map.is_damage_icon(new_x, new_y) {
-                self.player.points_de_vie -= 50;
-                self.map.clear_special_icon(new_x, new_y);
-                println!("🔥 Vous subissez 50 points de dégâts!");
-    
-                // Check if player dies
-                if self.player.points_de_vie <= 0 {
-                    self.state = GameState::GameOver;
-                }
+        // au bord de la carte, il n'y a pas de case voisine
+        let target = self.player.pos.step(direction).filter(|&pos| self.map.is_valid_move(pos));
+        let Some(pos) = target else {
+            return Ok(Turn::Refused(Refusal::Blocked));
+        };
+        self.stats.record_turn(self.floor());
+        let monster = self.find_monster_at(pos);
+
+        // En mode tactique on ne marche pas sur le monstre : le combat commence à côté
+        if let (Some(monster_idx), CombatMode::Tactical) = (monster, self.combat_mode) {
+            self.start_tactical_combat(monster_idx)?;
+            return Ok(Turn::Played);
+        }
+
+        self.enter_tile(pos)?;
+        // la porte et l'escalier ne servent qu'en exploration, jamais au milieu d'un combat
+        match monster {
+            Some(monster_idx) if self.state == GameState::Running => self.start_combat(monster_idx)?,
+            None if self.state == GameState::Running => self.take_stairs(pos)?,
+            _ => {},
+        }
+        Ok(Turn::Played)
+    }
+
+    // refuse une action en expliquant pourquoi dans le journal
+    pub fn refuse(&mut self, refusal: Refusal) -> Turn {
+        self.log.say(refusal.message(&self.player.name));
+        Turn::Refused(refusal)
+    }
+
+    // Déplace le joueur sur une case et applique l'effet des icônes (🍗, 🔥)
+    pub fn enter_tile(&mut self, pos: Position) -> Result<()> {
+        // les icônes sont lues avant que le joueur ne les recouvre
+        let health_icon = self.map.is_health_icon(pos);
+        let damage_icon = self.map.is_damage_icon(pos);
+        self.map.clear_special_icon(pos);
+
+        self.map.clear_position(self.player.pos)?;
+        self.player.pos = pos;
+        self.map.place_player(pos, &self.player)?;
+        self.player.regen_mana(2);
+
+        if health_icon {
+            self.player.heal(10);
+            self.log.say(t!("game.food", amount = 10));
+        }
+
+        if damage_icon {
+            let result = self.player.take_damage(Damage::new(50, DamageType::Fire));
+            self.stats.fire_tiles += 1;
+            self.notify(GameEvent::FireStepped);
+            self.stats.damage_taken += result.amount;
+            self.log.say(t!("game.fire_damage", amount = result.amount));
+            if let Some(message) = result.message() {
+                self.log.say(message);
             }
-    
-            if let Some(monster_idx) = self.find_monster_at(new_x, new_y) {
-                self.start_combat(monster_idx);
+
+            // Check if player dies
+            if !self.player.is_alive() {
+                self.state = GameState::GameOver;
+                self.death_cause = Some(t!("cause.burned"));
             }
         }
-    
-        // Existing door check remains the same
-        if self.map.is_on_door(self.player.x, self.player.y) {
-            self.generate_new_map();
-        }
+        Ok(())
     }
 
-    pub fn start_combat(&mut self, monster_idx: usize) {
+    pub fn start_combat(&mut self, monster_idx: usize) -> Result<()> {
+        let monster = self.monster(monster_idx)?;
+        let (monster_name, level, health, max_health) = (monster.species.name(), monster.level, monster.health, monster.max_health);
         self.state = GameState::Combat;
         self.current_monster_index = Some(monster_idx);
         
-        // Effacer l'écran
-        print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-        
-        let monster_name = match self.monsters[monster_idx].species {
-            MonsterSpecies::Goblin => "Gobelin",
-            MonsterSpecies::Orc => "Orc",
-            MonsterSpecies::Skeleton => "Squelette",
-            MonsterSpecies::Dragon => "Dragon",
-        };
-        
-        // Afficher l'introduction du combat avec une pause
-        println!("\n⚔️  Un {} niveau {} vous attaque!", 
-            monster_name,
-            self.monsters[monster_idx].level
-        );
-        std::thread::sleep(std::time::Duration::from_secs(1));
+        // Afficher l'introduction du combat (le rythme est donné par l'affichage)
+        self.log.say(t!("combat.encounter", monster = monster_name, level = level));
         
         // Afficher les statistiques initiales
-        println!("\n=== DÉBUT DU COMBAT ===");
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        let mut icon = ' ';
-        if self.player.espece == Espece::Homme {
-            icon = '🧑';
-        } else if self.player.espece  == Espece::Sorciere {
-            icon = '🧙';
-        } else if self.player.espece  == Espece::Elfe {
-            icon  = '🧚';
-        } else if self.player.espece  == Espece::Chevalier {
-            icon = '🧝';
-        }
-        println!("\n {} {}", icon, self.player.name);
-        println!("❤️  Points de vie: {}/{}", self.player.points_de_vie, self.player.max_health);
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        
-        println!("\n👾 {}", monster_name);
-        println!("❤️  Points de vie: {}/{}", 
-            self.monsters[monster_idx].health,
-            self.monsters[monster_idx].max_health
-        );
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        self.log.say(t!("combat.begin"));
+        self.log.say(format!("\n {} {}", self.player.espece.icon(), self.player.name));
+        self.log.say(t!("combat.health", health = self.player.points_de_vie, max = self.player.max_health));
         
-        println!("\nPréparez-vous au combat!");
+        self.log.say(format!("\n👾 {}", monster_name));
+        self.log.say(t!("combat.health", health = health, max = max_health));
         
-        // Pause finale pour s'assurer que tout est lisible
-        std::thread::sleep(std::time::Duration::from_secs(5));
+        self.log.say(t!("combat.prepare"));
+        self.notify(GameEvent::FightStarted);
+        Ok(())
     }
 
-    pub fn combat_turn(&mut self, player_action: PlayerCombatAction) {
-        if self.state != GameState::Combat || self.current_monster_index.is_none() {
-            return;
-        }
-    
-        let monster_idx = self.current_monster_index.unwrap();
-        let monster = &mut self.monsters[monster_idx];
-    
-        // Fonction helper pour faire une pause
-        fn combat_pause() {
-            std::thread::sleep(std::time::Duration::from_secs(1));
+    pub fn combat_turn(&mut self, player_action: PlayerCombatAction) -> Result<Turn> {
+        if self.state != GameState::Combat {
+            return Ok(Turn::Refused(Refusal::WrongState));
         }
-
-    
-        // Player's turn
-        println!("\n🗡️  Tour de {} !", self.player.name);
-        combat_pause();
-    
-        match player_action {
-            PlayerCombatAction::Attack => {
-                let damage = self.player.attack(monster);
-                println!("➜ {} prépare son attaque...", self.player.name);
-                combat_pause();
-                
-                monster.receive_damage(damage);
-                println!("➜ {} frappe et inflige {} points de dégâts au {} !", 
-                    self.player.name, 
-                    damage,
-                    match monster.species {
-                        MonsterSpecies::Goblin => "Gobelin",
-                        MonsterSpecies::Orc => "Orc",
-                        MonsterSpecies::Skeleton => "Squelette",
-                        MonsterSpecies::Dragon => "Dragon",
-                    }
-                );
-                combat_pause();
-                
-                println!("PV restants du monstre: {}", monster.health);
-                combat_pause();
-            },
-            PlayerCombatAction::SpecialAttack => {
-                if self.player.attaque_speciale {
-                    println!("➜ {} prépare une attaque spéciale...", self.player.name);
-                    combat_pause();
-                    
-                    let damage = self.player.use_special_attack(monster);
-                    println!("➜ {} déchaîne sa puissance et inflige {} points de dégâts !", 
-                        self.player.name, 
-                        damage
-                    );
-                    combat_pause();
-                    
-                    println!("PV restants du monstre: {}", monster.health);
-                    combat_pause();
-                } else {
-                    println!("❌ Attaque spéciale non disponible!");
-                    combat_pause();
-                    return;
-                }
-            },
-            PlayerCombatAction::Drink => {
-                let old_hp = self.player.points_de_vie;
-                println!("➜ {} sort une potion...", self.player.name);
-                combat_pause();
-                
-                self.player.drink_potion();
-                if self.player.points_de_vie > old_hp {
-                    println!("➜ {} boit la potion et récupère {} points de vie!", 
-                        self.player.name,
-                        self.player.points_de_vie - old_hp
-                    );
-                    combat_pause();
-                    
-                    println!("Nouveaux PV: {}", self.player.points_de_vie);
-                    combat_pause();
-                }
-            }
+        let Some(monster_idx) = self.current_monster_index else {
+            return Ok(Turn::Refused(Refusal::WrongState));
+        };
+        if player_action == PlayerCombatAction::SpecialAttack && !self.player.special_available() {
+            return Ok(self.refuse(Refusal::SpecialUnavailable));
         }
+        let monster = self.monsters.get_mut(monster_idx).ok_or(GameError::NoMonster(monster_idx))?;
     
-        // Monster's turn if still alive
-        if monster.is_alive() {
-            println!("\n👾 Tour du monstre:");
-            combat_pause();
-            
-            let mut rng = rand::thread_rng();
-            let monster_action: i32 = rng.gen_range(0..10);
-    
-            let monster_name = match monster.species {
-                MonsterSpecies::Goblin => "Gobelin",
-                MonsterSpecies::Orc => "Orc",
-                MonsterSpecies::Skeleton => "Squelette",
-                MonsterSpecies::Dragon => "Dragon",
-            };
-    
-            println!("➜ Le {} se prépare à attaquer...", monster_name);
-            combat_pause();
-    
-            let monster_damage = if monster_action < 2 && monster.special_attack_available {
-                monster.special_attack()
-            } else {
-                let damage = monster.attack(self.player.defense);
-                println!("➜ Le {} attaque et inflige {} points de dégâts!", 
-                    monster_name,
-                    damage
-                );
-                damage
-            };
-            combat_pause();
-    
-            self.player.receive_damage(monster_damage);
-            println!("PV restants de {}: {}", self.player.name, self.player.points_de_vie);
-            combat_pause();
+        // Player's turn, then the monster's if still alive
+        let Some((player_outcome, monster_outcome)) = combat::exchange(&mut self.player, monster, player_action, &mut self.rng, &mut self.log) else {
+            return Ok(Turn::Refused(Refusal::SpecialUnavailable));
+        };
+        self.stats.record_turn(self.floor());
+        self.stats.record_action(&player_action, &player_outcome);
+        self.stats.damage_taken += monster_outcome.damage;
+        if player_outcome.played {
+            self.notify(GameEvent::PlayerAction(player_action));
         }
+        let monster = &self.monsters[monster_idx];
     
         // Check combat end conditions
         if !monster.is_alive() {
-            println!("\n💫 Victoire!");
-            combat_pause();
-            println!("➜ +{} points d'expérience", monster.level * 10);
-            combat_pause();
-            self.end_combat(monster_idx);
-        } else if self.player.points_de_vie <= 0 {
-            println!("\n💀 Vous avez été vaincu!");
-            combat_pause();
+            self.log.say(t!("combat.victory"));
+            self.log.say(t!("combat.experience", points = monster.level * 10));
+            self.end_combat(monster_idx)?;
+        } else if !self.player.is_alive() {
+            self.log.say(t!("combat.defeat"));
             self.state = GameState::GameOver;
+            self.death_cause = Some(t!("cause.killed_by", monster = monster.species.name(), level = monster.level));
         }
-    
-        // Final pause before next turn
-        combat_pause();
+        Ok(Turn::Played)
     }
 
-    fn end_combat(&mut self, monster_idx: usize) {
-        println!("Victoire! +{} points", self.monsters[monster_idx].level * 10);
-        self.score += self.monsters[monster_idx].level * 10;
+    // signale un événement au suivi des succès ; les déblocages vont au journal
+    pub fn notify(&mut self, event: GameEvent) {
+        for achievement in self.achievements.observe(event) {
+            self.log.say(t!("achievement.unlocked", name = achievement.name(), description = achievement.description()));
+        }
+    }
+
+    // fin de la partie telle qu'elle apparaît dans les scores
+    pub fn outcome(&self) -> String {
+        match (self.state, &self.death_cause) {
+            (GameState::Win, _) => t!("outcome.victory"),
+            (GameState::GameOver, Some(cause)) => cause.clone(),
+            (GameState::GameOver, None) => t!("outcome.death"),
+            _ => t!("outcome.abandon"),
+        }
+    }
+
+    pub fn end_combat(&mut self, monster_idx: usize) -> Result<()> {
+        let monster = self.monster(monster_idx)?;
+        let (species, level) = (monster.species, monster.level);
+        self.log.say(t!("combat.points", points = level * 10));
+        self.score += level * 10;
+        self.stats.record_kill(species);
+        self.notify(GameEvent::FightWon { species, level });
         
         
         // Remove monster from map and list
         let monster = self.monsters.remove(monster_idx);
-        self.map.clear_position(monster.x, monster.y);
+        if monster.pos != self.player.pos {
+            self.map.clear_position(monster.pos)?;
+        }
         
         self.state = GameState::Running;
         self.current_monster_index = None;
+        Ok(())
     }
 
-    pub fn display(&self) {
-        println!("Joueur: {} (Niveau {})", self.player.name, self.player.level);
-        println!("Score: {}", self.score);
-        
-        if let Some(monster_idx) = self.current_monster_index {
-            let monster = &self.monsters[monster_idx];
-            println!("\nCombat contre {} (Niveau {})", 
-                match monster.species {
-                    MonsterSpecies::Goblin => "Gobelin",
-                    MonsterSpecies::Orc => "Orc",
-                    MonsterSpecies::Skeleton => "Squelette",
-                    MonsterSpecies::Dragon => "Dragon",
-                },
-                monster.level
-            );
-            println!();
-            println!("Monstre -> Points de vie {}/{}", monster.health, monster.max_health);
-            println!("{} -> Points de vie {}/{}", self.player.name, self.player.points_de_vie, self.player.max_health);
-            println!();
-            
+    // lignes de l'inventaire, affichées par l'interface dans une fenêtre
+    pub fn inventory_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            t!("inventory.title", name = self.player.name),
+            t!("sheet.potions", count = self.player.potions),
+        ];
+        if let Some(weapon) = self.player.ranged_weapon() {
+            match weapon.ammo {
+                Ammo::Arrows => lines.push(t!("inventory.arrows", weapon = weapon.name(), count = self.player.fleches)),
+                Ammo::Mana(_) => lines.push(t!("inventory.mana", weapon = weapon.name(), mana = self.player.mana, max = self.player.max_mana)),
+            }
         }
-        
-    
-        self.map.display();
-    }
-
-    pub fn show_inventory(&mut self) {
-        println!("Inventaire de {}", self.player.name);
-        println!("Points de vie: {}/{}", self.player.points_de_vie, self.player.max_health);
-        println!("Potions: {}", self.player.potions);
-        println!("Espèce: {}", match self.player.espece {
-            Espece::Homme => "Hommme",
-            Espece::Sorciere => "Sorcière",
-            Espece::Elfe => "Elfe",
-            Espece::Chevalier => "Chevalier",
-        });
-        println!("Attaque: {}", self.player.attack);
-        println!("Défense: {}", self.player.defense);
-    }
-
-    pub fn show_help(&mut self) {
-        println!("En mode normal:");
-        println!("z/flèche haut: Monter");
-        println!("s/flèche bas: Descendre");
-        println!("q/flèche gauche: Aller à gauche");
-        println!("d/flèche droite: Aller à droite");
-        println!("i: Afficher l'inventaire");
-        println!("h: Afficher l'aide");
-        println!("x: Quitter le jeu");
-        
-        println!("\nEn mode combat:");
-        println!("a: Attaque simple");
-        println!("s: Attaque spéciale");
-        println!("p: Boire une potion");
+        lines.push(t!("sheet.armor", armor = self.player.armure.name()));
+        lines
+    }
+
+    // fiche du personnage : caractéristiques, résistances et bilan de la partie
+    pub fn character_lines(&self) -> Vec<String> {
+        let player = &self.player;
+        let mut lines = vec![
+            format!("{} {} — {}", player.espece.icon(), player.name, player.espece.name()),
+            t!("sheet.level", level = player.level, score = self.score),
+            if player.attaque_speciale { t!("sheet.special_ready") } else { t!("sheet.special_used") },
+        ];
+        lines.extend(player.sheet_lines());
+        lines.push(String::new());
+        lines.push(t!("sheet.kills", kills = self.stats.total_kills(), turns = self.stats.total_turns()));
+        lines.push(t!("sheet.damage", dealt = self.stats.damage_dealt, taken = self.stats.damage_taken));
+        lines
+    }
+
+    // aide construite à partir des touches choisies
+    pub fn help_lines(bindings: &Bindings) -> Vec<String> {
+        let key = |action| bindings.key_label(action);
+        let mut lines = vec![t!("help.normal")];
+        lines.extend(bindings.help_lines(&[
+            Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight, Action::MoveUpLeft, Action::MoveUpRight,
+            Action::MoveDownLeft, Action::MoveDownRight, Action::Aim, Action::Inventory,
+            Action::CharacterSheet, Action::Speed, Action::Tileset, Action::Minimap, Action::Dungeon, Action::Help, Action::Abandon,
+        ]));
+        lines.extend([
+            t!("help.skip"),
+            t!("help.scroll"),
+            t!("help.pause"),
+            String::new(),
+            t!("help.combat"),
+        ]);
+        lines.extend(bindings.help_lines(&[Action::Attack, Action::Special, Action::Drink]));
+        lines.extend([
+            String::new(),
+            t!("help.targeting"),
+            t!("help.cycle_target"),
+            t!("help.fire", key = key(Action::Aim)),
+            t!("help.cancel"),
+            String::new(),
+            t!("help.tactical"),
+            t!("help.tactical_move", keys = format!("{}/{}/{}/{}", key(Action::MoveUp), key(Action::MoveLeft), key(Action::MoveDown), key(Action::MoveRight))),
+        ]);
+        lines.extend(bindings.help_lines(&[Action::Attack, Action::Special, Action::Drink, Action::Wait]));
+        lines
+    }
+
+    // étage en cours, 1 pour le premier
+    pub fn floor(&self) -> u32 {
+        self.dungeon.depth as u32 + 1
     }
-    // generate a new map if the player is on a door tile 
 
+    // cases découvertes sur tous les étages visités
+    pub fn explored_count(&self) -> usize {
+        self.map.explored_count() + self.dungeon.stored_floors().map(|floor| floor.map.explored_count()).sum::<usize>()
+    }
+
+    // la porte descend, l'escalier remonte ; ailleurs il ne se passe rien
+    pub fn take_stairs(&mut self, pos: Position) -> Result<()> {
+        if self.map.is_on_door(pos) {
+            self.descend()
+        } else if self.map.is_on_stairs_up(pos) {
+            self.change_floor(self.dungeon.depth - 1)?;
+            self.log.say(t!("dungeon.up", floor = self.floor()));
+            Ok(())
+        } else {
+            Ok(())
+        }
+    }
+
+    // Un étage déjà visité est repris tel quel. Un nouvel étage fait monter le joueur
+    // de niveau, et le dernier gagne la partie.
+    pub fn descend(&mut self) -> Result<()> {
+        let depth = self.dungeon.depth + 1;
+        if depth < self.dungeon.floor_count() {
+            self.change_floor(depth)?;
+            self.log.say(t!("dungeon.down", floor = self.floor()));
+            return Ok(());
+        }
 
-    pub fn generate_new_map(&mut self) {
         self.player.level_up();
         
-        // Clear screen
-        print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-        
         // Display level up message with benefits
-        println!("\n🆙 PASSAGE AU NIVEAU {} 🆙", self.player.level);
-        println!(" ");
-        println!("• Points de vie max augmentés");
-        println!("• Attaque améliorée");
-        println!("• Défense renforcée");
-        println!("• Attaque spéciale réinitialisée");
-        println!("• Une nouvelle potion ajoutée");
-        
-        // Pause to let the player read the message
-        std::thread::sleep(std::time::Duration::from_secs(3));
+        self.log.say(t!("level.up", level = self.player.level));
+        self.log.say(" ");
+        for key in ["level.health", "level.attack", "level.defense", "level.special", "level.potion"] {
+            self.log.say(t!(key));
+        }
     
+        let last = self.player.level == LAST_LEVEL;
+        self.notify(GameEvent::FloorReached { level: self.player.level, last });
+
         // Check if the game is won
-        if self.player.level == 5 {
+        if last {
             self.state = GameState::Win;
+            self.notify(GameEvent::Victory(self.player.espece));
         } else {
-            // Generate a new map with the same size
-            self.map = Map::new(10, 10);
-        
-            // Place the player at the starting position
-            self.map.place_player(0, 0, &self.player);
-            self.player.x = 0;
-            self.player.y = 0;
-        
-            // Reset monsters
-            self.monsters.clear();
-            self.spawn_random_monster();
+            self.change_floor(depth)?;
+            self.spawn_random_monster()?;
         }
+        Ok(())
     }
 
-    
-}
\ No newline at end of file
+    // Range l'étage en cours dans le donjon et passe à l'étage `depth`, généré s'il est nouveau.
+    // En descendant, le joueur arrive sur l'escalier ; en remontant, sur la porte.
+    fn change_floor(&mut self, depth: usize) -> Result<()> {
+        let down = depth > self.dungeon.depth;
+        self.map.clear_position(self.player.pos)?;
+        let next = self.dungeon.take(depth).unwrap_or_else(|| Floor {
+            map: Map::new(self.map_width, self.map_height, depth > 0, &mut self.rng),
+            monsters: Vec::new(),
+        });
+        let previous = Floor {
+            map: std::mem::replace(&mut self.map, next.map),
+            monsters: std::mem::replace(&mut self.monsters, next.monsters),
+        };
+        self.dungeon.store(self.dungeon.depth, previous);
+        self.dungeon.depth = depth;
+
+        self.player.pos = if down { self.map.entrance() } else { self.map.door() };
+        self.map.place_player(self.player.pos, &self.player)
+    }
+
+    // vue d'ensemble : une ligne par étage visité
+    pub fn dungeon_lines(&self) -> Vec<String> {
+        let mut lines = vec![t!("dungeon.title", floors = self.dungeon.floor_count(), last = LAST_LEVEL), String::new()];
+        for depth in 0..self.dungeon.floor_count() {
+            let (map, monsters) = match self.dungeon.floor(depth) {
+                Some(floor) => (&floor.map, &floor.monsters),
+                None => (&self.map, &self.monsters),
+            };
+            let explored = map.explored_count() * 100 / (map.width() * map.height());
+            let door = if map.is_explored(map.door()) { t!("dungeon.door_found") } else { t!("dungeon.door_unknown") };
+            lines.push(t!(
+                "dungeon.floor",
+                marker = if depth == self.dungeon.depth { "▶" } else { " " },
+                floor = depth + 1, explored = explored, monsters = monsters.len(), door = door,
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(map: &Map) -> impl Iterator<Item = Position> + '_ {
+        (0..map.height()).flat_map(move |y| (0..map.width()).map(move |x| Position::new(x, y)))
+    }
+
+    // ce qui doit survivre à un aller-retour : monstres, 🍗 restants et cases découvertes
+    struct Snapshot {
+        monsters: Vec<(Position, i32, u32)>,
+        food: Vec<Position>,
+        explored: Vec<Position>,
+    }
+
+    fn snapshot(game: &Game) -> Snapshot {
+        Snapshot {
+            monsters: game.monsters.iter().map(|monster| (monster.pos, monster.health, monster.level)).collect(),
+            food: positions(&game.map).filter(|&pos| game.map.is_health_icon(pos)).collect(),
+            explored: positions(&game.map).filter(|&pos| game.map.is_explored(pos)).collect(),
+        }
+    }
+
+    #[test]
+    fn floor_is_kept_when_going_down_and_back_up() {
+        let mut game = Game::with_seed(11);
+        game.initialize_player("Test", Espece::Chevalier).unwrap();
+        game.log = MessageLog::silent();
+        game.spawn_random_monster().unwrap();
+        game.spawn_random_monster().unwrap();
+        game.monsters[0].health = 3;
+        let before = snapshot(&game);
+        let door = game.map.door();
+
+        game.take_stairs(door).unwrap();
+        assert_eq!(game.floor(), 2);
+        assert_eq!(game.dungeon.floor_count(), 2);
+        let level = game.player.level;
+        let stairs = game.map.stairs_up().expect("escalier");
+        assert_eq!(game.player.pos, stairs);
+
+        game.take_stairs(stairs).unwrap();
+        assert_eq!(game.floor(), 1);
+        assert_eq!(game.dungeon.floor_count(), 2);
+        assert_eq!(game.player.pos, door);
+
+        let after = snapshot(&game);
+        assert_eq!(after.monsters, before.monsters);
+        assert_eq!(after.food, before.food);
+        // arriver sur la porte découvre ses alentours, mais rien ne s'oublie
+        assert!(before.explored.iter().all(|pos| after.explored.contains(pos)));
+
+        // redescendre retrouve l'étage déjà généré, sans nouveau niveau
+        game.take_stairs(door).unwrap();
+        assert_eq!(game.floor(), 2);
+        assert_eq!(game.dungeon.floor_count(), 2);
+        assert_eq!(game.player.level, level);
+    }
+}
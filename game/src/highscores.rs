@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::Game;
+use crate::player::Espece;
+use crate::storage;
+use crate::t;
+
+// Tableau des meilleurs scores : une partie terminée par ligne JSON dans highscores.jsonl.
+// On ne fait qu'ajouter des lignes (sous verrou), donc deux parties qui se terminent
+// en même temps ne peuvent pas s'écraser ; une ligne illisible est ignorée.
+
+const FILE_NAME: &str = "highscores.jsonl";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HighScore {
+    pub name: String,
+    pub espece: String,
+    pub score: u32,
+    // étage atteint
+    pub depth: u32,
+    // cause de la mort, ou victoire
+    pub cause: String,
+    pub seed: u64,
+    pub date: String,
+}
+
+impl HighScore {
+    pub fn from_game(game: &Game) -> Self {
+        HighScore {
+            name: game.player.name.clone(),
+            espece: game.player.espece.id().to_string(),
+            score: game.score,
+            depth: game.player.level,
+            cause: game.outcome(),
+            seed: game.seed,
+            date: today(),
+        }
+    }
+}
+
+pub fn record(entry: &HighScore) -> io::Result<()> {
+    let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    storage::append_line(&storage::data_file(FILE_NAME)?, &line)
+}
+
+// ordre du tableau : du meilleur au moins bon score ; à score égal, le plus profond d'abord
+pub fn compare(a: &HighScore, b: &HighScore) -> Ordering {
+    b.score.cmp(&a.score).then(b.depth.cmp(&a.depth))
+}
+
+// Place d'une partie (1 = la meilleure), calculée sans supposer la liste triée : on compte
+// les parties strictement devant elle. Des parties à égalité de score et d'étage partagent la place.
+pub fn rank(entries: &[HighScore], entry: &HighScore) -> usize {
+    1 + entries.iter().filter(|other| compare(other, entry) == Ordering::Less).count()
+}
+
+// triées dans l'ordre du tableau
+pub fn load() -> io::Result<Vec<HighScore>> {
+    let lines = storage::read_lines(&storage::data_file(FILE_NAME)?)?;
+    let mut entries: Vec<HighScore> = lines.iter()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    entries.sort_by(compare);
+    Ok(entries)
+}
+
+// les lignes du tableau, éventuellement limité à une espèce
+pub fn table(entries: &[HighScore], espece: Option<Espece>, limit: usize) -> Vec<String> {
+    let mut lines = vec![format!("{:>3}  {:<12} {:<10} {:>6} {:>5}  {:<10} {:<28} {}",
+        "#", t!("scores.name"), t!("scores.espece"), t!("scores.score"), t!("scores.depth"), t!("scores.date"), t!("scores.cause"), t!("scores.seed"))];
+    let selected = entries.iter()
+        .filter(|entry| espece.is_none_or(|espece| entry.espece == espece.id()))
+        .take(limit);
+    for (rank, entry) in selected.enumerate() {
+        lines.push(format!("{:>3}  {:<12} {:<10} {:>6} {:>5}  {:<10} {:<28} {}",
+            rank + 1,
+            entry.name.chars().take(12).collect::<String>(),
+            Espece::parse(&entry.espece).map_or(entry.espece.clone(), |espece| espece.name()),
+            entry.score,
+            entry.depth,
+            entry.date,
+            entry.cause.chars().take(28).collect::<String>(),
+            entry.seed
+        ));
+    }
+    if lines.len() == 1 {
+        lines.push(t!("scores.empty"));
+    }
+    lines
+}
+
+// date du jour (UTC) au format AAAA-MM-JJ
+pub fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// jours depuis le 1er janvier 1970 -> (année, mois, jour), calendrier grégorien
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u32, depth: u32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            espece: String::from("elfe"),
+            score,
+            depth,
+            cause: String::new(),
+            seed: 0,
+            date: String::new(),
+        }
+    }
+
+    fn names(entries: &[HighScore]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn table_order_is_score_then_depth() {
+        let mut entries = vec![entry("a", 10, 2), entry("b", 30, 1), entry("c", 10, 4), entry("d", 0, 5), entry("e", 30, 3)];
+        entries.sort_by(compare);
+        assert_eq!(names(&entries), ["e", "b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn rank_does_not_depend_on_the_order() {
+        let entries = vec![entry("a", 10, 2), entry("b", 30, 1), entry("c", 10, 4), entry("d", 0, 5), entry("e", 30, 3)];
+        let mut sorted = entries.clone();
+        sorted.sort_by(compare);
+        // (score, étage, place attendue)
+        let cases = [(30, 3, 1), (30, 1, 2), (10, 4, 3), (10, 2, 4), (10, 3, 4), (0, 5, 5), (0, 1, 6), (50, 1, 1)];
+        for (score, depth, expected) in cases {
+            let new = entry("nouveau", score, depth);
+            assert_eq!(rank(&entries, &new), expected, "score {score}, étage {depth}");
+            assert_eq!(rank(&sorted, &new), expected, "score {score}, étage {depth}");
+        }
+    }
+
+    #[test]
+    fn ties_share_the_rank() {
+        let entries = vec![entry("a", 20, 3), entry("b", 20, 3), entry("c", 40, 1)];
+        assert_eq!(rank(&entries, &entries[0]), 2);
+        assert_eq!(rank(&entries, &entries[1]), 2);
+        assert_eq!(rank(&entries, &entries[2]), 1);
+    }
+}
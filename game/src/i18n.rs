@@ -0,0 +1,132 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::storage;
+
+// Textes du jeu : un catalogue par langue (locales/*.json), des clés et des paramètres
+// nommés entre accolades. t!("combat.victory", points = 10) cherche la clé dans la langue
+// choisie, puis en français ; une clé absente des deux catalogues s'affiche telle quelle.
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Locale {
+    Fr,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Fr, Locale::En];
+
+    // code court, accepté par --lang
+    pub fn id(&self) -> &'static str {
+        match self {
+            Locale::Fr => "fr",
+            Locale::En => "en",
+        }
+    }
+
+    // chaque langue est affichée dans sa propre langue
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::Fr => "français",
+            Locale::En => "English",
+        }
+    }
+
+    // « en », « en_GB.UTF-8 », « fr-CA »...
+    pub fn parse(value: &str) -> Option<Locale> {
+        let language = value.split(['_', '-', '.']).next()?.to_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.id() == language)
+    }
+
+    // langue de l'environnement, dans l'ordre de priorité habituel
+    pub fn from_env() -> Option<Locale> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::parse(&value))
+    }
+
+    pub fn next(&self) -> Locale {
+        match self {
+            Locale::Fr => Locale::En,
+            Locale::En => Locale::Fr,
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::Fr => include_str!("../locales/fr.json"),
+            Locale::En => include_str!("../locales/en.json"),
+        }
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+static CATALOGUES: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+// clés déjà signalées, pour ne prévenir qu'une fois
+static MISSING: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+// en version de débogage, les clés manquantes sont notées dans ce fichier du dossier de données
+const MISSING_LOG: &str = "i18n-missing.log";
+
+pub fn locale() -> Locale {
+    Locale::ALL[LOCALE.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_locale(locale: Locale) {
+    let index = Locale::ALL.iter().position(|&other| other == locale).unwrap_or(0);
+    LOCALE.store(index as u8, Ordering::Relaxed);
+}
+
+fn catalogue(locale: Locale) -> &'static HashMap<String, String> {
+    let catalogues = CATALOGUES.get_or_init(|| {
+        Locale::ALL.iter()
+            // les catalogues sont compilés avec le jeu : un fichier invalide est une erreur de développement
+            .map(|locale| serde_json::from_str(locale.source()).expect("catalogue de textes invalide"))
+            .collect()
+    });
+    let index = Locale::ALL.iter().position(|&other| other == locale).unwrap_or(0);
+    &catalogues[index]
+}
+
+fn warn_missing(key: &str, locale: Locale) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let mut missing = MISSING.lock().unwrap_or_else(|error| error.into_inner());
+    // pas de sortie d'erreur : en plein écran, le terminal est en mode brut et l'affichage serait abîmé
+    if missing.insert(format!("{}:{}", locale.id(), key)) {
+        let line = format!("clé « {} » absente du catalogue {}, texte français utilisé", key, locale.id());
+        let _ = storage::data_file(MISSING_LOG).and_then(|path| storage::append_line(&path, &line));
+    }
+}
+
+// texte de la clé dans la langue choisie, paramètres remplacés
+pub fn text(key: &str, args: &[(&str, String)]) -> String {
+    let locale = locale();
+    let template = match catalogue(locale).get(key) {
+        Some(template) => template,
+        None => {
+            warn_missing(key, locale);
+            match catalogue(Locale::Fr).get(key) {
+                Some(template) => template,
+                None => return key.to_string(),
+            }
+        },
+    };
+    let mut text = template.clone();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::text($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::text($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+use crate::position::Direction;
+use crate::storage;
+use crate::t;
+
+// Touches du jeu : chaque action a une touche réglable, enregistrée dans keys.json.
+// Les flèches (déplacement), entrée (tirer) et échap (pause, annuler) marchent toujours,
+// quelle que soit la disposition choisie.
+
+const FILE_NAME: &str = "keys.json";
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    MoveUpLeft,
+    MoveUpRight,
+    MoveDownLeft,
+    MoveDownRight,
+    Attack,
+    Special,
+    Drink,
+    Wait,
+    Aim,
+    Inventory,
+    CharacterSheet,
+    Speed,
+    Tileset,
+    Minimap,
+    Dungeon,
+    Help,
+    Abandon,
+}
+
+impl Action {
+    pub const ALL: [Action; 21] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::MoveUpLeft,
+        Action::MoveUpRight,
+        Action::MoveDownLeft,
+        Action::MoveDownRight,
+        Action::Attack,
+        Action::Special,
+        Action::Drink,
+        Action::Wait,
+        Action::Aim,
+        Action::Inventory,
+        Action::CharacterSheet,
+        Action::Speed,
+        Action::Tileset,
+        Action::Minimap,
+        Action::Dungeon,
+        Action::Help,
+        Action::Abandon,
+    ];
+
+    // nom dans keys.json et clé du catalogue de textes
+    fn id(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::MoveUpLeft => "move_up_left",
+            Action::MoveUpRight => "move_up_right",
+            Action::MoveDownLeft => "move_down_left",
+            Action::MoveDownRight => "move_down_right",
+            Action::Attack => "attack",
+            Action::Special => "special",
+            Action::Drink => "drink",
+            Action::Wait => "wait",
+            Action::Aim => "aim",
+            Action::Inventory => "inventory",
+            Action::CharacterSheet => "character_sheet",
+            Action::Speed => "speed",
+            Action::Tileset => "tileset",
+            Action::Minimap => "minimap",
+            Action::Dungeon => "dungeon",
+            Action::Help => "help",
+            Action::Abandon => "abandon",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        t!(&format!("action.{}", self.id()))
+    }
+
+    // direction d'un déplacement
+    pub fn direction(&self) -> Option<Direction> {
+        match self {
+            Action::MoveUp => Some(Direction::Up),
+            Action::MoveDown => Some(Direction::Down),
+            Action::MoveLeft => Some(Direction::Left),
+            Action::MoveRight => Some(Direction::Right),
+            Action::MoveUpLeft => Some(Direction::UpLeft),
+            Action::MoveUpRight => Some(Direction::UpRight),
+            Action::MoveDownLeft => Some(Direction::DownLeft),
+            Action::MoveDownRight => Some(Direction::DownRight),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    Azerty,
+    Qwerty,
+    Vi,
+    Custom,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 3] = [Preset::Azerty, Preset::Qwerty, Preset::Vi];
+
+    pub fn name(&self) -> String {
+        match self {
+            Preset::Azerty => t!("preset.azerty"),
+            Preset::Qwerty => t!("preset.qwerty"),
+            Preset::Vi => t!("preset.vi"),
+            Preset::Custom => t!("preset.custom"),
+        }
+    }
+
+    pub fn next(&self) -> Preset {
+        match self {
+            Preset::Azerty => Preset::Qwerty,
+            Preset::Qwerty => Preset::Vi,
+            Preset::Vi | Preset::Custom => Preset::Azerty,
+        }
+    }
+
+    // touches dans l'ordre de Action::ALL ; les diagonales sont sur le pavé numérique
+    fn keys(&self) -> [char; 21] {
+        match self {
+            Preset::Azerty | Preset::Custom => ['z', 's', 'q', 'd', '7', '9', '1', '3', 'a', 'e', 'p', ' ', 'f', 'i', 'c', 'v', 't', 'm', 'o', 'h', 'x'],
+            Preset::Qwerty => ['w', 's', 'a', 'd', '7', '9', '1', '3', 'e', 'r', 'q', ' ', 'f', 'i', 'c', 'v', 't', 'm', 'o', 'h', 'x'],
+            Preset::Vi => ['k', 'j', 'h', 'l', 'y', 'u', 'b', 'n', 'a', 'e', 'p', ' ', 'f', 'i', 'c', 'v', 't', 'm', 'o', '?', 'x'],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Bindings {
+    pub preset: Preset,
+    keys: BTreeMap<Action, char>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::preset(Preset::Azerty)
+    }
+}
+
+// nom affiché d'une touche
+pub fn key_name(key: char) -> String {
+    match key {
+        ' ' => t!("key.space"),
+        _ => key.to_string(),
+    }
+}
+
+impl Bindings {
+    pub fn preset(preset: Preset) -> Self {
+        Bindings { preset, keys: Action::ALL.into_iter().zip(preset.keys()).collect() }
+    }
+
+    // keys.json s'il existe, sinon la disposition azerty
+    pub fn load() -> io::Result<Self> {
+        Bindings::load_from(&storage::data_file(FILE_NAME)?)
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Bindings::default()),
+            Err(error) => return Err(error),
+        };
+        let mut bindings: Bindings = serde_json::from_str(&contents)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        // une action absente du fichier (ajoutée depuis) garde la touche azerty si elle est libre
+        for (action, key) in Bindings::default().keys {
+            if !bindings.keys.contains_key(&action) && bindings.action(KeyCode::Char(key)).is_none() {
+                bindings.keys.insert(action, key);
+            }
+        }
+        Ok(bindings)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        self.save_to(&storage::data_file(FILE_NAME)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        storage::write_atomic(path, &contents)
+    }
+
+    pub fn key(&self, action: Action) -> Option<char> {
+        self.keys.get(&action).copied()
+    }
+
+    // « z » ou « - » si l'action n'a pas de touche
+    pub fn key_label(&self, action: Action) -> String {
+        self.key(action).map_or("-".to_string(), key_name)
+    }
+
+    // action associée à une touche, flèches comprises
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Up => Some(Action::MoveUp),
+            KeyCode::Down => Some(Action::MoveDown),
+            KeyCode::Left => Some(Action::MoveLeft),
+            KeyCode::Right => Some(Action::MoveRight),
+            KeyCode::Char(c) => {
+                let c = c.to_ascii_lowercase();
+                self.keys.iter().find(|(_, &key)| key == c).map(|(&action, _)| action)
+            },
+            _ => None,
+        }
+    }
+
+    // Associe la touche à l'action. Si elle servait déjà à une autre action, les deux
+    // actions échangent leurs touches : une touche ne déclenche jamais deux actions.
+    pub fn bind(&mut self, action: Action, key: char) {
+        let key = key.to_ascii_lowercase();
+        let previous = self.key(action);
+        if let Some(other) = self.action(KeyCode::Char(key)) {
+            match previous {
+                Some(previous) => self.keys.insert(other, previous),
+                None => self.keys.remove(&other),
+            };
+        }
+        self.keys.insert(action, key);
+        self.preset = Preset::Custom;
+    }
+
+    // une ligne « touche: description » par action, pour l'aide
+    pub fn help_lines(&self, actions: &[Action]) -> Vec<String> {
+        actions.iter()
+            .map(|&action| {
+                let arrow = match action {
+                    Action::MoveUp => t!("key.up"),
+                    Action::MoveDown => t!("key.down"),
+                    Action::MoveLeft => t!("key.left"),
+                    Action::MoveRight => t!("key.right"),
+                    _ => String::new(),
+                };
+                let key = if arrow.is_empty() { self.key_label(action) } else { format!("{}/{}", self.key_label(action), arrow) };
+                format!("{}: {}", key, action.description())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // chaque touche ne sert qu'à une action
+    fn keys_are_unique(bindings: &Bindings) -> bool {
+        let keys: Vec<char> = Action::ALL.iter().filter_map(|&action| bindings.key(action)).collect();
+        keys.iter().enumerate().all(|(i, key)| !keys[i + 1..].contains(key))
+    }
+
+    #[test]
+    fn presets_have_one_key_per_action() {
+        for preset in Preset::ALL {
+            let bindings = Bindings::preset(preset);
+            assert!(Action::ALL.iter().all(|&action| bindings.key(action).is_some()), "{preset:?}");
+            assert!(keys_are_unique(&bindings), "{preset:?}");
+        }
+    }
+
+    #[test]
+    fn binding_a_taken_key_swaps_the_two_actions() {
+        let mut bindings = Bindings::preset(Preset::Azerty);
+        bindings.bind(Action::Attack, 'Z');
+        assert_eq!(bindings.key(Action::Attack), Some('z'));
+        assert_eq!(bindings.key(Action::MoveUp), Some('a'));
+        assert_eq!(bindings.action(KeyCode::Char('z')), Some(Action::Attack));
+        assert_eq!(bindings.action(KeyCode::Char('a')), Some(Action::MoveUp));
+        assert_eq!(bindings.preset, Preset::Custom);
+        assert!(keys_are_unique(&bindings));
+    }
+
+    #[test]
+    fn binding_a_free_key_leaves_the_others() {
+        let mut bindings = Bindings::preset(Preset::Qwerty);
+        bindings.bind(Action::Attack, 'g');
+        assert_eq!(bindings.key(Action::Attack), Some('g'));
+        assert_eq!(bindings.action(KeyCode::Char('e')), None);
+        let qwerty = Bindings::preset(Preset::Qwerty);
+        assert!(Action::ALL.iter().filter(|&&action| action != Action::Attack).all(|&action| bindings.key(action) == qwerty.key(action)));
+        assert!(keys_are_unique(&bindings));
+    }
+
+    #[test]
+    fn binding_file_round_trips() {
+        let mut custom = Bindings::preset(Preset::Vi);
+        custom.bind(Action::Drink, 'k');
+        let cases: Vec<Bindings> = Preset::ALL.into_iter().map(Bindings::preset).chain([custom]).collect();
+        for (i, bindings) in cases.iter().enumerate() {
+            let path = std::env::temp_dir().join(format!("mini-rpg-keys-test-{}-{}.json", std::process::id(), i));
+            bindings.save_to(&path).unwrap();
+            let loaded = Bindings::load_from(&path);
+            fs::remove_file(&path).unwrap();
+            let loaded = loaded.unwrap();
+            assert_eq!(loaded.preset, bindings.preset);
+            assert!(Action::ALL.iter().all(|&action| loaded.key(action) == bindings.key(action)), "{:?}", bindings.preset);
+        }
+    }
+
+    #[test]
+    fn missing_file_gives_azerty() {
+        let path = std::env::temp_dir().join(format!("mini-rpg-keys-test-{}-absent.json", std::process::id()));
+        let bindings = Bindings::load_from(&path).unwrap();
+        assert_eq!(bindings.preset, Preset::Azerty);
+        assert_eq!(bindings.key(Action::MoveUp), Some('z'));
+    }
+}
@@ -0,0 +1,32 @@
+pub mod achievements;
+pub mod app;
+pub mod autoplay;
+pub mod cli;
+pub mod bot;
+pub mod clock;
+pub mod combat;
+pub mod combatant;
+pub mod damage;
+pub mod dungeon;
+pub mod env;
+pub mod equipment;
+pub mod error;
+pub mod game;
+pub mod highscores;
+pub mod i18n;
+pub mod keys;
+pub mod log;
+pub mod map;
+pub mod monster;
+pub mod morgue;
+pub mod pacing;
+pub mod player;
+pub mod position;
+pub mod replay;
+pub mod save;
+pub mod stats;
+pub mod storage;
+pub mod tactical;
+pub mod targeting;
+pub mod tileset;
+pub mod ui;
@@ -0,0 +1,46 @@
+// Journal des messages du jeu.
+// La logique du jeu ne fait qu'y écrire : c'est l'affichage qui montre les messages,
+// à son rythme (voir pacing.rs). En mode console chaque message est en plus imprimé
+// dès qu'il est écrit, sans attente.
+
+const MAX_MESSAGES: usize = 200;
+
+pub struct MessageLog {
+    pub messages: Vec<String>,
+    pub echo: bool,
+    // nombre total de messages écrits depuis le début, y compris ceux déjà oubliés
+    written: usize,
+}
+
+impl MessageLog {
+    pub fn console() -> Self {
+        MessageLog { messages: Vec::new(), echo: true, written: 0 }
+    }
+
+    pub fn silent() -> Self {
+        MessageLog { messages: Vec::new(), echo: false, written: 0 }
+    }
+
+    pub fn say(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if self.echo {
+            println!("{}", message);
+        }
+        self.messages.push(message);
+        self.written += 1;
+        if self.messages.len() > MAX_MESSAGES {
+            self.messages.remove(0);
+        }
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    // les `count` derniers messages parmi les `upto` premiers écrits
+    pub fn recent(&self, upto: usize, count: usize) -> &[String] {
+        let forgotten = self.written - self.messages.len();
+        let end = upto.clamp(forgotten, self.written) - forgotten;
+        &self.messages[end.saturating_sub(count)..end]
+    }
+}
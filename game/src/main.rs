@@ -1,186 +1,180 @@
-use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use crossterm::{
-    event::{self, Event, KeyCode},
-    terminal::{self, ClearType},
-    ExecutableCommand,
-};
-
-mod game;
-mod map;
-mod player;
-mod monster;
-
-use game::{Game, GameState, PlayerCombatAction};
-use player::Espece;
-
-fn select_character() -> (String, Espece) {
-   
-    let mut input = String::new();
-
-    // Get player name
-    println!("Bienvenue dans le Mini-RPG!");
-    println!("Entrez votre nom:");
-    std::io::stdin().read_line(&mut input).unwrap();
-    let name = input.trim().to_string();
-
-    // Clear terminal for character selection
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    
-    // Display character selection menu
-    // add smybol for each character
-
-
-
+use game::app::{App, Settings};
+use game::autoplay;
+use game::bot;
+use game::cli::{self, Mode, Options};
+use game::clock::SpawnTiming;
+use game::error::Result;
+use game::game::{CombatMode, Game, GameState, DEFAULT_MAP_HEIGHT, DEFAULT_MAP_WIDTH};
+use game::i18n::{self, Locale};
+use game::keys::Bindings;
+use game::log::MessageLog;
+use game::player::Espece;
+use game::replay::{self, Replay};
+use game::t;
+use game::tileset::Tileset;
+use game::ui::{self, Screen, View};
+
+// Joueur automatique : nombre maximum d'actions
+const AUTOPLAY_MAX_STEPS: u32 = 5000;
+
+// la partie décrite par les options, sans passer par les menus
+fn game_from_options(options: &Options, name: &str, espece: Espece) -> Result<Game> {
+    let (width, height) = options.map_size.unwrap_or((DEFAULT_MAP_WIDTH, DEFAULT_MAP_HEIGHT));
+    let mut game = Game::with_map_size(options.seed.unwrap_or_else(rand::random), width, height);
+    game.initialize_player(options.name.as_deref().unwrap_or(name), options.espece.unwrap_or(espece))?;
+    game.combat_mode = options.combat_mode.unwrap_or(CombatMode::Classic);
+    game.clock.timing = options.timing.unwrap_or(SpawnTiming::RealTime);
+    game.difficulty = options.difficulty.unwrap_or_default();
+    Ok(game)
+}
 
-    println!("Choisissez votre perso:");
-    println!("1. Homme 🧑");
-    println!("   Force brute et résistance exceptionnelle");
-    println!("   PV: 120, Attaque: 15, Défense: 10");
-    println!();
-    
-    println!("2. Sorcière 🧙");
-    println!("   Maîtrise de la magie et capacité de soin");
-    println!("   PV: 80, Attaque: 12, Défense: 5");
-    println!();
-    
-    println!("3. Elfe 🧚");
-    println!("   Agilité et puissance d'attaque supérieure");
-    println!("   PV: 100, Attaque: 18, Défense: 7");
-    println!();
-    
-    println!("4. Chevalier 🧝");
-    println!("   Equilibre entre attaque et défense");
-    println!("   PV: 150, Attaque: 16, Défense: 12");
-    
-    // Get character choice
-    loop {
-        input.clear();
-        println!("\nEntrez votre choix (1-4):");
-        std::io::stdin().read_line(&mut input).unwrap();
-        
-        match input.trim() {
-            "1" => return (name, Espece::Homme),
-            "2" => return (name, Espece::Sorciere),
-            "3" => return (name, Espece::Elfe),
-            "4" => return (name, Espece::Chevalier),
-            _ => println!("Choix invalide, veuillez réessayer."),
+// Fait avancer la partie action par action jusqu'à ce que `step` s'arrête,
+// avec ou sans affichage ; renvoie le nombre d'actions jouées.
+// L'écran est refermé en sortant, même sur une erreur.
+fn watch(game: &mut Game, options: &Options, commands: &str, mut step: impl FnMut(&mut Game) -> Result<bool>) -> Result<u32> {
+    let mut screen = if options.headless { None } else { Some(Screen::open()?) };
+    let tileset = options.tileset.unwrap_or(Tileset::Emoji);
+    let mut steps = 0;
+    while step(game)? {
+        steps += 1;
+        if let Some(screen) = &mut screen {
+            let view = View { shown: game.log.written(), scroll: 0, overlay: None, commands, tileset, minimap: false };
+            screen.draw(game, &view)?;
+            thread::sleep(Duration::from_millis(options.delay));
         }
     }
+    Ok(steps)
 }
 
-fn main() -> crossterm::Result<()> {
-    let game = Arc::new(Mutex::new(Game::new()));
-    let game_clone = Arc::clone(&game);
-
-    // Thread pour les événements aléatoires (spawn de monstres)
-    thread::spawn(move || {
-        loop {
-            thread::sleep(Duration::from_secs(5));
-            let mut game = game_clone.lock().unwrap();
-            if game.state == GameState::Running {
-                game.spawn_random_monster();
-            }
-        }
-    });
-
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    
-    // Get player name and character choice
-    let (name, espece) = select_character();
-
-    {
-        let mut game = game.lock().unwrap();
-        game.initialize_player(&name, espece);
+fn result(game: &Game) -> String {
+    match game.state {
+        GameState::Win => t!("result.win"),
+        GameState::GameOver => t!("result.lost"),
+        _ => t!("result.interrupted"),
     }
+}
 
-    // Activation du mode brut pour les déplacements
-    terminal::enable_raw_mode()?;
-    loop {
-        let mut stdout = std::io::stdout();
-        stdout.execute(terminal::Clear(ClearType::All))?;
-        {
-            let game = game.lock().unwrap();
-            print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+// --autoplay [--headless] [--speed MS] : une partie jouée par le joueur automatique
+fn run_autoplay(options: &Options) -> Result<()> {
+    let espece = Espece::ALL[rand::random::<usize>() % Espece::ALL.len()];
+    let mut game = game_from_options(options, &t!("autoplay.name"), espece)?;
+    let mut played = 0;
+    let steps = watch(&mut game, options, &t!("autoplay.title"), |game| {
+        let playing = played < AUTOPLAY_MAX_STEPS
+            && matches!(game.state, GameState::Running | GameState::Combat | GameState::Targeting);
+        if playing {
+            played += 1;
+            autoplay::play_step(game)?;
+            game.tick()?;
+        }
+        Ok(playing)
+    })?;
+    let result = if steps >= AUTOPLAY_MAX_STEPS { t!("result.too_long") } else { result(&game) };
+    println!("{}", t!(
+        "autoplay.result",
+        name = game.player.name, espece = game.player.espece.name(), result = result,
+        score = game.score, level = game.player.level, steps = steps,
+    ));
+    Ok(())
+}
 
-            game.display();   
-            
-            
-            if game.state == GameState::Win {
-                //clear screen
-                print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-                println!();
-                println!("🏆 FÉLICITATIONS ! Vous avez atteint le niveau 5 et remporté le jeu avce le score {}!", game.score);
-                
-            }
+// --replay FICHIER [--headless] [--speed MS] : rejoue une partie enregistrée
+fn run_replay(replay: &Replay, options: &Options) -> Result<()> {
+    let mut game = replay.game()?;
+    let mut commands = replay.commands.iter().copied();
+    let steps = watch(&mut game, options, &t!("replay.title"), |game| replay::step(game, &mut commands))?;
+    println!("{}", t!(
+        "replay.result",
+        name = game.player.name, espece = game.player.espece.name(), seed = game.seed, result = result(&game),
+        score = game.score, level = game.player.level, steps = steps,
+    ));
+    Ok(())
+}
 
-            if game.state == GameState::GameOver {
-                print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-                println!();
-                println!("Game Over! Score final: {}, Niveau atteint : {}", game.score , game.player.level);
-                break;
-            }
+// --bot-protocol [--seed N] [--class ESPECE] [--tactical] : partie pilotée en JSON sur stdin/stdout
+fn run_bot_protocol(options: &Options) -> Result<()> {
+    let mut game = game_from_options(options, &t!("bot.name"), Espece::Homme)?;
+    game.log = MessageLog::silent();
 
-         
+    let stdin = std::io::stdin();
+    bot::run(game, stdin.lock(), std::io::stdout())
+}
 
-            match game.state {
-                GameState::Running => {
-                    println!("\nCommandes: (z)haut (s)bas (q)gauche (d)droite (i)inventaire (h)aide (x)quitter");
-                },
-                GameState::Combat => {
-                    println!("\nCommandes de combat: (a)ttaque (s)péciale (p)otion (x)quitter");
-                },
-                GameState::GameOver => break,
-                GameState::Win => break,
-            }
+// partie jouée au clavier, menus compris
+fn play(options: &Options) -> Result<()> {
+    let defaults = Settings::default();
+    // un keys.json illisible n'empêche pas de jouer : on garde la disposition azerty
+    let bindings = Bindings::load().unwrap_or_else(|error| {
+        eprintln!("{}", t!("main.bindings_failed", error = error));
+        Bindings::default()
+    });
+    let (map_width, map_height) = options.map_size.unwrap_or((defaults.map_width, defaults.map_height));
+    let mut app = App::new(Settings {
+        combat_mode: options.combat_mode.unwrap_or(defaults.combat_mode),
+        timing: options.timing.unwrap_or(defaults.timing),
+        tileset: options.tileset.unwrap_or(defaults.tileset),
+        map_width,
+        map_height,
+        difficulty: options.difficulty.unwrap_or(defaults.difficulty),
+        seed: options.seed,
+        bindings,
+        locale: i18n::locale(),
+        ..defaults
+    });
+    // avec --load, --name ou --class, la partie commence sans passer par les menus
+    if let Some(path) = &options.load {
+        if let Err(error) = app.load_game_from(path) {
+            eprintln!("{}", t!("main.load_failed", path = path.display(), error = error));
+            std::process::exit(1);
         }
+    } else if options.name.is_some() || options.espece.is_some() {
+        app.start_game(&options.name.clone().unwrap_or_else(|| t!("name.default")), options.espece.unwrap_or(Espece::Homme))?;
+    }
 
-      
-        if let Event::Key(key_event) = event::read()? {
-            let mut game = game.lock().unwrap();
-            match game.state {
-                GameState::Running => {
-                    match key_event.code {
-                        KeyCode::Up | KeyCode::Char('z') => game.move_player(0, -1),
-                        KeyCode::Down | KeyCode::Char('s') => game.move_player(0, 1),
-                        KeyCode::Left | KeyCode::Char('q') => game.move_player(-1, 0),
-                        KeyCode::Right | KeyCode::Char('d') => game.move_player(1, 0),
-                        KeyCode::Char('i') => {
-                            game.show_inventory();
-                            std::thread::sleep(std::time::Duration::from_secs(2));
-                        },
-                        KeyCode::Char('h') => {
-                            game.show_help();
-                            std::thread::sleep(std::time::Duration::from_secs(2));
-                        },
-                        KeyCode::Char('x') => break,
-                        _ => {}
-                    }
-                },
-                GameState::Combat => {
-                    match key_event.code {
-                        KeyCode::Char('a') => game.combat_turn(PlayerCombatAction::Attack),
-                        KeyCode::Char('s') => game.combat_turn(PlayerCombatAction::SpecialAttack),
-                        KeyCode::Char('p') => game.combat_turn(PlayerCombatAction::Drink),
-                        KeyCode::Char('h') => {
-                            game.show_help();
-                            std::thread::sleep(std::time::Duration::from_secs(2));
-                        },
-                        KeyCode::Char('x') => break,
-                        _ => {}
-                    }
-                },
-
+    // Mode brut : menus et partie se jouent touche par touche.
+    // Le terminal est rendu quand `screen` est lâché, que la partie se termine ou non.
+    let mut screen = Screen::open_raw()?;
+    app.run(&mut screen)
+}
 
-                GameState::GameOver => break,
-                GameState::Win => break,
-            }
-        }
+fn main() {
+    ui::install_panic_hook();
+    // langue de l'environnement d'abord : les erreurs d'options sont déjà traduites
+    i18n::set_locale(Locale::from_env().unwrap_or(Locale::Fr));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match cli::parse(&args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", t!("main.error", error = error), cli::usage());
+            std::process::exit(2);
+        },
+    };
+    if let Some(locale) = options.locale {
+        i18n::set_locale(locale);
+    }
+    if options.help {
+        println!("{}", cli::usage());
+        return;
     }
 
-    // Désactivation du mode brut après le jeu
-    terminal::disable_raw_mode()?;
-    Ok(())
-}
\ No newline at end of file
+    let result = match &options.mode {
+        Mode::BotProtocol => run_bot_protocol(&options),
+        Mode::Autoplay => run_autoplay(&options),
+        Mode::Replay(path) => match Replay::load(path) {
+            Ok(replay) => run_replay(&replay, &options),
+            Err(error) => {
+                eprintln!("{}", t!("main.replay_failed", path = path.display(), error = error));
+                std::process::exit(1);
+            },
+        },
+        Mode::Play => play(&options),
+    };
+    // l'écran est déjà refermé : le message s'affiche dans le terminal normal
+    if let Err(error) = result {
+        eprintln!("{}", t!("main.error", error = error));
+        std::process::exit(1);
+    }
+}
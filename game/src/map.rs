@@ -1,42 +1,77 @@
 use rand::Rng;
-use crate::player::{Player, Espece};
+use serde::{Deserialize, Serialize};
+use crate::error::{GameError, Result};
+use crate::player::Player;
+use crate::position::Position;
 
+// rayon (en cases) découvert autour du joueur, pour la mini-carte
+const SIGHT_RADIUS: usize = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Map {
     width: usize,
     height: usize,
     tiles: Vec<Vec<char>>,
+    explored: Vec<Vec<bool>>,
+    // la porte (🚪) descend à l'étage suivant, l'escalier (🔼) remonte au précédent
+    door: Position,
+    stairs_up: Option<Position>,
+    // case d'arrivée en descendant : l'escalier, ou l'entrée du donjon au premier étage
+    entrance: Position,
 }
 
 impl Map {
-    pub fn new(width: usize, height: usize) -> Self {
+    // `stairs_up` : false pour le premier étage, qui n'a rien au-dessus
+    pub fn new(width: usize, height: usize, stairs_up: bool, rng: &mut impl Rng) -> Self {
         let mut map = Map {
             width,
             height,
             tiles: vec![vec!['⬛'; width]; height],
+            explored: vec![vec![false; width]; height],
+            door: Position::default(),
+            stairs_up: None,
+            entrance: Position::default(),
         };
-        // add a door to the map the bottom right corner
-        map.tiles[map.height - 1][map.width - 1] = '🚪';
-        map.generate_walls_and_icons();
+        map.generate_walls(rng);
+        // la porte et l'escalier sont posés après les murs, qui ne peuvent plus les recouvrir
+        map.door = map.random_floor_tile(rng);
+        map.tiles[map.door.y()][map.door.x()] = '🚪';
+        if stairs_up {
+            let stairs = map.random_floor_tile(rng);
+            map.tiles[stairs.y()][stairs.x()] = '🔼';
+            map.stairs_up = Some(stairs);
+        }
+        map.generate_icons(rng);
+        // choisie après les icônes : le joueur n'arrive jamais sur un 🍗 ou un 🔥
+        map.entrance = map.stairs_up.unwrap_or_else(|| map.random_floor_tile(rng));
         map
     }
 
-    fn generate_walls_and_icons(&mut self) {
-        let mut rng = rand::thread_rng();
-        
-        // Number of heart and flame icons to generate
-        let num_hearts = rng.gen_range(1..4);
-        let num_flames = rng.gen_range(1..4);
+    // une case vide au hasard ; les murs en laissent toujours
+    fn random_floor_tile(&self, rng: &mut impl Rng) -> Position {
+        loop {
+            let pos = Position::new(rng.gen_range(0..self.width), rng.gen_range(0..self.height));
+            if self.is_empty(pos) {
+                return pos;
+            }
+        }
+    }
 
-        // Generate walls
-        for _ in 0..self.width {
+    fn generate_walls(&mut self, rng: &mut impl Rng) {
+        for _ in 0..self.width * self.height / 10 {
             let x = rng.gen_range(0..self.width);
             let y = rng.gen_range(0..self.height);
-            if x != self.width - 1 && y != self.height - 1 {
-                self.tiles[y][x] = '⬜';
-            } else {
-                self.tiles[y][x] = '⬛';
-            }
+            self.tiles[y][x] = '⬜';
         }
+    }
+
+    fn generate_icons(&mut self, rng: &mut impl Rng) {
+        // les grandes cartes ont proportionnellement autant d'icônes qu'une 10x10
+        let scale = (self.width * self.height / 100).max(1);
+
+        // Number of heart and flame icons to generate
+        let num_hearts = rng.gen_range(1..4) * scale;
+        let num_flames = rng.gen_range(1..4) * scale;
 
         // Place heart icons (heal 10 HP)
         for _ in 0..num_hearts {
@@ -63,72 +98,168 @@ impl Map {
         }
     }
 
-    pub fn display(&self) {
-        for row in &self.tiles {
-            for tile in row {
-                print!("{} ", tile);
-            }
-            println!();
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // les cases et les cases découvertes ont bien `height` lignes de `width` colonnes
+    // (une carte relue d'une sauvegarde n'est pas forcément passée par `new`)
+    pub fn is_well_formed(&self) -> bool {
+        self.tiles.len() == self.height
+            && self.explored.len() == self.height
+            && self.tiles.iter().all(|row| row.len() == self.width)
+            && self.explored.iter().all(|row| row.len() == self.width)
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x() < self.width && pos.y() < self.height
+    }
+
+    pub fn door(&self) -> Position {
+        self.door
+    }
+
+    pub fn stairs_up(&self) -> Option<Position> {
+        self.stairs_up
+    }
+
+    pub fn entrance(&self) -> Position {
+        self.entrance
+    }
+
+    // ce qui reste sur une case quand on la quitte : la porte et l'escalier ne s'effacent pas
+    fn ground(&self, pos: Position) -> char {
+        if pos == self.door {
+            '🚪'
+        } else if Some(pos) == self.stairs_up {
+            '🔼'
+        } else {
+            '⬛'
         }
     }
 
-    pub fn is_valid_move(&self, x: usize, y: usize) -> bool {
-        x < self.width && y < self.height && self.tiles[y][x] != '⬜'
+    // hors de la carte, tout se passe comme contre un mur
+    pub fn tile(&self, pos: Position) -> char {
+        self.tiles.get(pos.y()).and_then(|row| row.get(pos.x())).copied().unwrap_or('⬜')
+    }
+
+    fn tile_mut(&mut self, pos: Position) -> Result<&mut char> {
+        self.tiles.get_mut(pos.y()).and_then(|row| row.get_mut(pos.x())).ok_or(GameError::OutOfMap(pos))
     }
 
-    pub fn place_player(&mut self, x: usize, y: usize, player: &Player) {
+    pub fn is_valid_move(&self, pos: Position) -> bool {
+        self.contains(pos) && self.tile(pos) != '⬜'
+    }
+
+    pub fn place_player(&mut self, pos: Position, player: &Player) -> Result<()> {
         // choisir le bon caractère pour le joueur
-        if player.espece == Espece::Homme {
-            self.tiles[y][x] = '🧑';
-        } else if player.espece == Espece::Sorciere {
-            self.tiles[y][x] = '🧙';
-        } else if player.espece == Espece::Elfe {
-            self.tiles[y][x] = '🧚';
-        } else if player.espece == Espece::Chevalier {
-            self.tiles[y][x] = '🧝';
+        *self.tile_mut(pos)? = player.espece.icon();
+        self.reveal_around(pos);
+        Ok(())
+    }
+
+    fn reveal_around(&mut self, pos: Position) {
+        for row in pos.y().saturating_sub(SIGHT_RADIUS)..(pos.y() + SIGHT_RADIUS + 1).min(self.height) {
+            for col in pos.x().saturating_sub(SIGHT_RADIUS)..(pos.x() + SIGHT_RADIUS + 1).min(self.width) {
+                self.explored[row][col] = true;
+            }
         }
     }
 
-    pub fn place_monster(&mut self, x: usize, y: usize) {
-        self.tiles[y][x] = '👾';
+    pub fn is_explored(&self, pos: Position) -> bool {
+        self.explored.get(pos.y()).and_then(|row| row.get(pos.x())).copied().unwrap_or(false)
     }
 
-    pub fn clear_position(&mut self, x: usize, y: usize) {
-        self.tiles[y][x] = '⬛';
+    pub fn explored_count(&self) -> usize {
+        self.explored.iter().flatten().filter(|&&seen| seen).count()
     }
 
-    pub fn get_random_empty_position(&self) -> (usize, usize) {
-        let mut rng = rand::thread_rng();
-        loop {
-            let x = rng.gen_range(0..self.width);
-            let y = rng.gen_range(0..self.height);
-            if self.tiles[y][x] == '⬛' {
-                return (x, y);
-            }
+    pub fn place_monster(&mut self, pos: Position) -> Result<()> {
+        *self.tile_mut(pos)? = '👾';
+        Ok(())
+    }
+
+    pub fn place_hazard(&mut self, pos: Position) -> Result<()> {
+        *self.tile_mut(pos)? = '🔥';
+        Ok(())
+    }
+
+    pub fn clear_position(&mut self, pos: Position) -> Result<()> {
+        let ground = self.ground(pos);
+        *self.tile_mut(pos)? = ground;
+        Ok(())
+    }
+
+    // None quand la carte est pleine (monstres et 🔥 s'accumulent au fil des ticks)
+    pub fn get_random_empty_position(&self, rng: &mut impl Rng) -> Option<Position> {
+        if !self.tiles.iter().flatten().any(|&tile| tile == '⬛') {
+            return None;
         }
+        Some(self.random_floor_tile(rng))
     }
 
     // check if the player is on a door
-    pub fn is_on_door(&self, x: usize, y: usize) -> bool {
-        // Vérifier que les coordonnées sont dans la carte
-        x < self.width && y < self.height && 
-        // Vérifier spécifiquement la porte dans le coin inférieur droit
-        (x == self.width - 1 && y == self.height - 1)
+    pub fn is_on_door(&self, pos: Position) -> bool {
+        pos == self.door
     }
 
-    pub fn is_health_icon(&self, x: usize, y: usize) -> bool {
-        x < self.width && y < self.height && self.tiles[y][x] == '🍗'
+    pub fn is_on_stairs_up(&self, pos: Position) -> bool {
+        Some(pos) == self.stairs_up
     }
 
-    pub fn is_damage_icon(&self, x: usize, y: usize) -> bool {
-        x < self.width && y < self.height && self.tiles[y][x] == '🔥'
+    pub fn is_health_icon(&self, pos: Position) -> bool {
+        self.contains(pos) && self.tile(pos) == '🍗'
     }
 
-    pub fn clear_special_icon(&mut self, x: usize, y: usize) {
-        if x < self.width && y < self.height && 
-           (self.tiles[y][x] == '🍗' || self.tiles[y][x] == '🔥') {
-            self.tiles[y][x] = '⬛';
+    pub fn is_damage_icon(&self, pos: Position) -> bool {
+        self.contains(pos) && self.tile(pos) == '🔥'
+    }
+
+    pub fn is_empty(&self, pos: Position) -> bool {
+        self.contains(pos) && self.tile(pos) == '⬛'
+    }
+
+    pub fn is_wall(&self, pos: Position) -> bool {
+        self.contains(pos) && self.tile(pos) == '⬜'
+    }
+
+    // ligne de vue (Bresenham) : seuls les murs entre les deux cases la bloquent
+    pub fn line_of_sight(&self, from: Position, to: Position) -> bool {
+        let (mut x, mut y) = (from.x() as i32, from.y() as i32);
+        let (x1, y1) = (to.x() as i32, to.y() as i32);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        // on avance entre deux cases de la carte : x et y restent positifs
+        while (x, y) != (x1, y1) {
+            let pos = Position::new(x as usize, y as usize);
+            if pos != from && self.is_wall(pos) {
+                return false;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
         }
+        true
     }
 
-}
\ No newline at end of file
+    pub fn clear_special_icon(&mut self, pos: Position) {
+        if self.is_health_icon(pos) || self.is_damage_icon(pos) {
+            self.tiles[pos.y()][pos.x()] = '⬛';
+        }
+    }
+
+}
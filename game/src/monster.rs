@@ -1,6 +1,12 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use crate::combatant::Combatant;
+use crate::damage::{self, CombatStats, Damage, DamageResult, DamageType, Resistances, Roll};
+use crate::game::PlayerCombatAction;
+use crate::position::Position;
+use crate::t;
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum MonsterSpecies {
     Goblin,
     Orc,
@@ -8,9 +14,101 @@ pub enum MonsterSpecies {
     Dragon,
 }
 
+impl MonsterSpecies {
+    pub const ALL: [MonsterSpecies; 4] = [
+        MonsterSpecies::Goblin,
+        MonsterSpecies::Orc,
+        MonsterSpecies::Skeleton,
+        MonsterSpecies::Dragon,
+    ];
+
+    // identifiant stable, qui ne dépend pas de la langue (fichiers CSV)
+    pub fn id(&self) -> &'static str {
+        match self {
+            MonsterSpecies::Goblin => "goblin",
+            MonsterSpecies::Orc => "orc",
+            MonsterSpecies::Skeleton => "skeleton",
+            MonsterSpecies::Dragon => "dragon",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            MonsterSpecies::Goblin => t!("monster.goblin"),
+            MonsterSpecies::Orc => t!("monster.orc"),
+            MonsterSpecies::Skeleton => t!("monster.skeleton"),
+            MonsterSpecies::Dragon => t!("monster.dragon"),
+        }
+    }
+
+    // lettre du jeu de tuiles ASCII
+    pub fn letter(&self) -> char {
+        match self {
+            MonsterSpecies::Goblin => 'g',
+            MonsterSpecies::Orc => 'o',
+            MonsterSpecies::Skeleton => 's',
+            MonsterSpecies::Dragon => 'D',
+        }
+    }
+
+    pub fn resistances(&self) -> Resistances {
+        match self {
+            MonsterSpecies::Goblin => Resistances {
+                immune: &[],
+                resistant: &[DamageType::Poison],
+                weak: &[DamageType::Fire],
+            },
+            MonsterSpecies::Orc => Resistances {
+                immune: &[],
+                resistant: &[DamageType::Physical],
+                weak: &[DamageType::Poison],
+            },
+            MonsterSpecies::Skeleton => Resistances {
+                immune: &[DamageType::Poison],
+                resistant: &[DamageType::Frost],
+                weak: &[DamageType::Holy],
+            },
+            MonsterSpecies::Dragon => Resistances {
+                immune: &[DamageType::Fire],
+                resistant: &[DamageType::Physical],
+                weak: &[DamageType::Frost],
+            },
+        }
+    }
+
+    pub fn stats(&self) -> CombatStats {
+        let (accuracy, evasion, crit_chance, crit_multiplier) = match self {
+            MonsterSpecies::Goblin => (90, 15, 10, 200),
+            MonsterSpecies::Orc => (80, 5, 10, 250),
+            MonsterSpecies::Skeleton => (85, 10, 5, 200),
+            MonsterSpecies::Dragon => (90, 5, 10, 200),
+        };
+        CombatStats {
+            accuracy,
+            evasion,
+            crit_chance,
+            crit_multiplier,
+            block_chance: 0,
+            variance: 15,
+        }
+    }
+
+    // type de dégâts de l'attaque spéciale
+    pub fn special_damage_type(&self) -> DamageType {
+        match self {
+            MonsterSpecies::Goblin => DamageType::Poison,
+            MonsterSpecies::Orc => DamageType::Physical,
+            MonsterSpecies::Skeleton => DamageType::Frost,
+            MonsterSpecies::Dragon => DamageType::Fire,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Monster {
-    pub x: usize,
-    pub y: usize,
+    // aplatie : la sauvegarde garde ses champs x et y
+    #[serde(flatten)]
+    pub pos: Position,
     pub species: MonsterSpecies,
     pub level: u32,
     pub health: i32,
@@ -18,11 +116,11 @@ pub struct Monster {
     pub attack: i32,
     pub defense: i32,
     pub special_attack_available: bool,
+    pub stats: CombatStats,
 }
 
 impl Monster {
-    pub fn new(x: usize, y: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn new(pos: Position, rng: &mut impl Rng) -> Self {
         let level = rng.gen_range(1..=3);
         let species = match rng.gen_range(0..4) {
             0 => MonsterSpecies::Goblin,
@@ -30,7 +128,17 @@ impl Monster {
             2 => MonsterSpecies::Skeleton,
             _ => MonsterSpecies::Dragon,
         };
+        Monster::with_species(pos, species, level)
+    }
+
+    // difficulté : points de vie et attaque en pourcentage des valeurs normales
+    pub fn scale(&mut self, percent: i32) {
+        self.health = self.health * percent / 100;
+        self.max_health = self.max_health * percent / 100;
+        self.attack = self.attack * percent / 100;
+    }
 
+    pub fn with_species(pos: Position, species: MonsterSpecies, level: u32) -> Self {
         let (base_health, base_attack, base_defense) = match species {
             MonsterSpecies::Goblin => (50, 10, 5),
             MonsterSpecies::Orc => (80, 15, 8),
@@ -39,8 +147,7 @@ impl Monster {
         };
 
         Monster {
-            x,
-            y,
+            pos,
             species,
             level,
             health: base_health * level as i32,
@@ -48,53 +155,102 @@ impl Monster {
             attack: base_attack * level as i32,
             defense: base_defense * level as i32,
             special_attack_available: true,
+            stats: species.stats(),
         }
     }
 
-    pub fn attack(&mut self, target_defense: i32) -> i32 {
-        let mut rng = rand::thread_rng();
-        let damage = std::cmp::max(1, self.attack - target_defense);
-        let critical_chance: i32 = rng.gen_range(0..10);
-        
-        if critical_chance == 0 {
-            println!("Coup critique!");
-            damage * 2
-        } else {
-            damage
-        }
-    }
-
-    pub fn special_attack(&mut self) -> i32 {
+    fn special_damage(&mut self) -> Damage {
         if !self.special_attack_available {
-            return self.attack;
+            return Damage::physical(self.attack);
         }
 
         self.special_attack_available = false;
+        let kind = self.species.special_damage_type();
         match self.species {
-            MonsterSpecies::Goblin => {
-                println!("Le Gobelin effectue une attaque fourbe!");
-                self.attack * 2
-            },
-            MonsterSpecies::Orc => {
-                println!("L'Orc pousse un cri de guerre!");
-                self.attack * 3 / 2
-            },
-            MonsterSpecies::Skeleton => {
-                println!("Le Squelette lance une attaque spectrale!");
-                self.attack * 2
-            },
-            MonsterSpecies::Dragon => {
-                println!("Le Dragon crache des flammes!");
-                self.attack * 3
-            },
+            MonsterSpecies::Goblin => Damage::new(self.attack * 2, kind),
+            MonsterSpecies::Orc => Damage::new(self.attack * 3 / 2, kind),
+            MonsterSpecies::Skeleton => Damage::new(self.attack * 2, kind),
+            MonsterSpecies::Dragon => Damage::new(self.attack * 3, kind),
         }
     }
+}
 
-    pub fn receive_damage(&mut self, damage: i32) {
-        self.health = std::cmp::max(0, self.health - damage);
+impl Combatant for Monster {
+    fn name(&self) -> String {
+        self.species.name()
     }
 
-    pub fn is_alive(&self) -> bool {
-        self.health > 0
+    fn health(&self) -> i32 {
+        self.health
+    }
+
+    fn max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    fn defense(&self) -> i32 {
+        self.defense
+    }
+
+    fn stats(&self) -> &CombatStats {
+        &self.stats
+    }
+
+    fn take_damage(&mut self, damage: Damage) -> DamageResult {
+        let result = self.species.resistances().apply(damage);
+        self.health = std::cmp::max(0, self.health - result.amount);
+        result
+    }
+
+    fn heal(&mut self, amount: i32) -> i32 {
+        let old_health = self.health;
+        self.health = std::cmp::min(self.health + amount, self.max_health);
+        self.health - old_health
+    }
+
+    fn attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll {
+        let damage = Damage::physical(damage::base_damage(self.attack, target.defense()));
+        damage::roll_attack(rng, &self.stats, target.stats(), damage)
+    }
+
+    fn special_available(&self) -> bool {
+        self.special_attack_available
+    }
+
+    fn special_attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll {
+        let damage = self.special_damage();
+        damage::roll_attack(rng, &self.stats, target.stats(), damage)
+    }
+
+    // le Dragon crache ses flammes à distance
+    fn special_range(&self) -> i32 {
+        match self.species {
+            MonsterSpecies::Dragon => 3,
+            _ => 1,
+        }
+    }
+
+    fn special_message(&self) -> Option<String> {
+        let key = match self.species {
+            MonsterSpecies::Goblin => "monster.goblin.special",
+            MonsterSpecies::Orc => "monster.orc.special",
+            MonsterSpecies::Skeleton => "monster.skeleton.special",
+            MonsterSpecies::Dragon => "monster.dragon.special",
+        };
+        Some(t!(key))
+    }
+
+    // les monstres n'ont pas de potions
+    fn drink_potion(&mut self) -> Option<i32> {
+        None
+    }
+
+    // 20% de chances d'utiliser l'attaque spéciale tant qu'elle est disponible
+    fn choose_action(&self, _opponent: &dyn Combatant, rng: &mut dyn RngCore) -> PlayerCombatAction {
+        if rng.gen_range(0..10) < 2 && self.special_attack_available {
+            PlayerCombatAction::SpecialAttack
+        } else {
+            PlayerCombatAction::Attack
+        }
     }
 }
\ No newline at end of file
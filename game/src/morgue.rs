@@ -0,0 +1,69 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::game::Game;
+use crate::highscores;
+use crate::monster::MonsterSpecies;
+use crate::position::Position;
+use crate::storage;
+use crate::t;
+use crate::tileset::{Tile, Tileset};
+
+// Fichier « morgue » : le résumé texte d'une partie terminée (fiche du personnage,
+// statistiques, carte finale), écrit dans le dossier morgue/ des données du jeu.
+
+pub fn report(game: &Game) -> String {
+    let stats = &game.stats;
+    let mut lines = vec![
+        t!("morgue.title"),
+        t!("morgue.date", date = highscores::today(), seed = game.seed),
+        t!("morgue.outcome", outcome = game.outcome()),
+        t!("morgue.score", score = game.score, level = game.player.level),
+        String::new(),
+        t!("morgue.character"),
+    ];
+    lines.extend(game.character_lines());
+    lines.push(String::new());
+    lines.extend(game.inventory_lines());
+
+    lines.push(String::new());
+    lines.push(t!("morgue.stats"));
+    lines.push(t!("morgue.kills", count = stats.total_kills()));
+    for species in MonsterSpecies::ALL {
+        lines.push(format!("  {:<10} {}", species.name(), stats.kills[species as usize]));
+    }
+    lines.push(t!("morgue.damage_dealt", amount = stats.damage_dealt));
+    lines.push(t!("morgue.damage_taken", amount = stats.damage_taken));
+    lines.push(t!("morgue.potions", count = stats.potions));
+    lines.push(t!("morgue.specials", count = stats.specials));
+    lines.push(t!("morgue.explored", count = game.explored_count()));
+    lines.push(t!("morgue.fire", count = stats.fire_tiles));
+    lines.push(t!("morgue.turns", count = stats.total_turns()));
+    for (level, turns) in stats.turns_per_level.iter().enumerate() {
+        lines.push(t!("morgue.floor_turns", level = format!("{:<3}", level + 1), turns = turns));
+    }
+
+    lines.push(String::new());
+    lines.push(t!("morgue.map"));
+    for y in 0..game.map.height() {
+        let row: String = (0..game.map.width())
+            .map(|x| Tileset::Ascii.glyph(Tile::at(game, Position::new(x, y))))
+            .collect();
+        lines.push(row);
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+// écrit le résumé et renvoie le chemin du fichier
+pub fn write(game: &Game) -> io::Result<PathBuf> {
+    let dir = storage::data_dir().join("morgue");
+    fs::create_dir_all(&dir)?;
+    let name: String = game.player.name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("morgue-{}-{}-{}.txt", name, highscores::today(), game.seed));
+    fs::write(&path, report(game))?;
+    Ok(path)
+}
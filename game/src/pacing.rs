@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+use crate::log::MessageLog;
+use crate::t;
+
+// Rythme d'affichage des messages : la logique du jeu ne dort jamais, c'est
+// l'affichage qui dévoile les nouveaux messages un par un.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Speed {
+    Instant,
+    Fast,
+    Normal,
+}
+
+impl Speed {
+    pub fn name(&self) -> String {
+        match self {
+            Speed::Instant => t!("speed.instant"),
+            Speed::Fast => t!("speed.fast"),
+            Speed::Normal => t!("speed.normal"),
+        }
+    }
+
+    // délai entre deux messages
+    pub fn delay(&self) -> Duration {
+        match self {
+            Speed::Instant => Duration::ZERO,
+            Speed::Fast => Duration::from_millis(150),
+            Speed::Normal => Duration::from_millis(600),
+        }
+    }
+
+    pub fn next(&self) -> Speed {
+        match self {
+            Speed::Instant => Speed::Fast,
+            Speed::Fast => Speed::Normal,
+            Speed::Normal => Speed::Instant,
+        }
+    }
+}
+
+pub struct Pacer {
+    pub speed: Speed,
+    // nombre de messages du journal déjà montrés
+    shown: usize,
+    next_at: Instant,
+}
+
+impl Pacer {
+    pub fn new(speed: Speed, log: &MessageLog) -> Self {
+        Pacer { speed, shown: log.written(), next_at: Instant::now() }
+    }
+
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    // des messages restent à montrer : les touches (sauf « passer ») sont ignorées
+    pub fn is_animating(&self, log: &MessageLog) -> bool {
+        self.shown < log.written()
+    }
+
+    // dévoile le message suivant si son heure est venue ; renvoie true s'il faut redessiner
+    pub fn update(&mut self, log: &MessageLog) -> bool {
+        if !self.is_animating(log) {
+            return false;
+        }
+        if self.speed == Speed::Instant {
+            self.skip(log);
+            return true;
+        }
+        let now = Instant::now();
+        if now < self.next_at {
+            return false;
+        }
+        self.shown += 1;
+        self.next_at = now + self.speed.delay();
+        true
+    }
+
+    pub fn skip(&mut self, log: &MessageLog) {
+        self.shown = log.written();
+    }
+}
@@ -1,7 +1,13 @@
-use rand::Rng;
-use crate::monster::Monster;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use crate::combatant::Combatant;
+use crate::damage::{self, CombatStats, Damage, DamageResult, DamageType, Roll};
+use crate::equipment::{Ammo, Equipment, RangedWeapon};
+use crate::game::PlayerCombatAction;
+use crate::position::Position;
+use crate::t;
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum Espece {
     Homme,
     Sorciere,
@@ -9,10 +15,61 @@ pub enum Espece {
     Chevalier,
 }
 
+impl Espece {
+    pub const ALL: [Espece; 4] = [Espece::Homme, Espece::Sorciere, Espece::Elfe, Espece::Chevalier];
+
+    // nom enregistré dans les scores et le profil, quelle que soit la langue
+    pub fn id(&self) -> &'static str {
+        match self {
+            Espece::Homme => "Homme",
+            Espece::Sorciere => "Sorcière",
+            Espece::Elfe => "Elfe",
+            Espece::Chevalier => "Chevalier",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Espece::Homme => t!("espece.homme"),
+            Espece::Sorciere => t!("espece.sorciere"),
+            Espece::Elfe => t!("espece.elfe"),
+            Espece::Chevalier => t!("espece.chevalier"),
+        }
+    }
+
+    // nom accepté en ligne de commande (ou lu dans le profil), avec ou sans accent
+    pub fn parse(name: &str) -> Option<Espece> {
+        match name.to_lowercase().as_str() {
+            "homme" => Some(Espece::Homme),
+            "sorciere" | "sorcière" => Some(Espece::Sorciere),
+            "elfe" => Some(Espece::Elfe),
+            "chevalier" => Some(Espece::Chevalier),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Espece::Homme => t!("espece.homme.description"),
+            Espece::Sorciere => t!("espece.sorciere.description"),
+            Espece::Elfe => t!("espece.elfe.description"),
+            Espece::Chevalier => t!("espece.chevalier.description"),
+        }
+    }
+
+    pub fn icon(&self) -> char {
+        match self {
+            Espece::Homme => '🧑',
+            Espece::Sorciere => '🧙',
+            Espece::Elfe => '🧚',
+            Espece::Chevalier => '🧝',
+        }
+    }
+}
+
 pub struct Player {
     pub name: String,
-    pub x: usize,
-    pub y: usize,
+    pub pos: Position,
     pub points_de_vie: i32,
     pub max_health: i32,
     pub level: u32,
@@ -21,11 +78,33 @@ pub struct Player {
     pub espece: Espece,
     pub attaque_speciale: bool,
     pub potions: i32,
+    pub armure: Equipment,
+    pub stats: CombatStats,
+    pub fleches: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+}
+
+// Noms acceptés : lettres, chiffres, espaces, tirets et apostrophes
+pub const MAX_NAME_LENGTH: usize = 16;
+
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(t!("name.empty"));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(t!("name.too_long", max = MAX_NAME_LENGTH));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '\'' | '_')) {
+        return Err(t!("name.invalid"));
+    }
+    Ok(name.to_string())
 }
 
 impl Default for Player {
     fn default() -> Self {
-        Player::new("Aventurier")
+        Player::new(&t!("name.default"))
     }
 }
 
@@ -41,8 +120,7 @@ impl Player {
     
         Player {
             name: name.to_string(),
-            x: 0,
-            y: 0,
+            pos: Position::default(),
             points_de_vie: base_health,
             max_health: base_health,
             level: 1,
@@ -51,10 +129,15 @@ impl Player {
             espece,
             attaque_speciale: true,
             potions: 3,
+            armure: Equipment::starting_armor(espece),
+            stats: Player::class_stats(espece),
+            fleches: if espece == Espece::Elfe { 12 } else { 0 },
+            mana: if espece == Espece::Sorciere { 40 } else { 0 },
+            max_mana: if espece == Espece::Sorciere { 40 } else { 0 },
         }
     }
 
-
+    // personnage d'une espèce tirée au hasard
     pub fn new(name: &str) -> Self {
         let mut rng = rand::thread_rng();
         let espece = match rng.gen_range(0..4) {
@@ -63,88 +146,142 @@ impl Player {
             2 => Espece::Elfe,
             _ => Espece::Chevalier,
         };
+        // une seule table de caractéristiques : celle de new_with_class
+        Player::new_with_class(name, espece)
+    }
 
-        let (base_health, base_attack, base_defense) = match espece {
-            Espece::Homme => (120, 15, 10),
-            Espece::Sorciere => (80, 12, 5),
-            Espece::Elfe => (100, 18, 7),
-            Espece::Chevalier => (150, 16, 12),
-        };
-
-        Player {
-            name: name.to_string(),
-            x: 0,
-            y: 0,
-            points_de_vie: base_health,
-            max_health: base_health,
-            level: 1,
-            attack: base_attack,
-            defense: base_defense,
-            espece,
-            attaque_speciale: true,
-            potions: 3,
+    // précision, esquive, critiques et blocage de chaque espèce
+    pub fn class_stats(espece: Espece) -> CombatStats {
+        match espece {
+            Espece::Homme => CombatStats {
+                accuracy: 95,
+                evasion: 10,
+                crit_chance: 10,
+                crit_multiplier: 200,
+                block_chance: 0,
+                variance: 15,
+            },
+            Espece::Sorciere => CombatStats {
+                accuracy: 100,
+                evasion: 10,
+                crit_chance: 15,
+                crit_multiplier: 175,
+                block_chance: 0,
+                variance: 20,
+            },
+            Espece::Elfe => CombatStats {
+                accuracy: 100,
+                evasion: 35,
+                crit_chance: 20,
+                crit_multiplier: 200,
+                block_chance: 0,
+                variance: 10,
+            },
+            Espece::Chevalier => CombatStats {
+                accuracy: 90,
+                evasion: 5,
+                crit_chance: 5,
+                crit_multiplier: 200,
+                block_chance: 25,
+                variance: 10,
+            },
         }
     }
 
-    pub fn attack(&mut self, monster: &mut Monster) -> i32 {
-        let mut rng = rand::thread_rng();
-        let base_damage = std::cmp::max(1, self.attack - monster.defense);
-        let critical_chance: i32 = rng.gen_range(0..10);
-        
-        let damage = if critical_chance == 0 {
-            println!("Coup critique de {}!", self.name);
-            base_damage * 2
-        } else {
-            base_damage
-        };
-        damage
+    // type de dégâts de l'attaque spéciale de chaque espèce
+    pub fn special_damage_type(&self) -> DamageType {
+        match self.espece {
+            Espece::Homme => DamageType::Physical,
+            Espece::Sorciere => DamageType::Fire,
+            Espece::Elfe => DamageType::Poison,
+            Espece::Chevalier => DamageType::Holy,
+        }
     }
 
-    pub fn use_special_attack(&mut self, monster: &mut Monster) -> i32 {
+    fn special_damage(&mut self) -> Damage {
         if !self.attaque_speciale {
-            println!("Attaque spéciale non disponible!");
-            return self.attack;
+            return Damage::physical(self.attack);
         }
 
         self.attaque_speciale = false;
+        let kind = self.special_damage_type();
         match self.espece {
             Espece::Homme => {
-                monster.receive_damage(self.attack * 2);
-                self.attack * 2
+                Damage::new(self.attack * 2, kind)
             },
             Espece::Sorciere => {
                 let damage = self.attack;
-                self.points_de_vie = std::cmp::min(self.points_de_vie + 20, self.max_health);
-                damage
+                self.heal(20);
+                Damage::new(damage, kind)
             },
             Espece::Elfe => {
         
                 self.attack *= 2;
-                self.attack
+                Damage::new(self.attack, kind)
             },
             Espece::Chevalier => {
                 
-                self.points_de_vie = std::cmp::min(self.points_de_vie + 10, self.max_health);
-                self.attack * 2
+                self.heal(10);
+                Damage::new(self.attack * 2, kind)
             },
         }
     }
 
-    pub fn receive_damage(&mut self, damage: i32) {
-        self.points_de_vie = std::cmp::max(0, self.points_de_vie - damage);
-        
+    // caractéristiques de la fiche du personnage, aussi montrées à la création
+    pub fn sheet_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            t!("sheet.health", health = self.points_de_vie, max = self.max_health),
+            t!("sheet.attack", attack = self.attack, defense = self.defense),
+        ];
+        if self.max_mana > 0 {
+            lines.push(t!("sheet.mana", mana = self.mana, max = self.max_mana));
+        }
+        lines.push(t!("sheet.accuracy", accuracy = self.stats.accuracy, evasion = self.stats.evasion));
+        lines.push(t!("sheet.critical", chance = self.stats.crit_chance, multiplier = format!("{:.2}", self.stats.crit_multiplier as f32 / 100.0)));
+        if self.stats.block_chance > 0 {
+            lines.push(t!("sheet.block", chance = self.stats.block_chance));
+        }
+        if let Some(weapon) = self.ranged_weapon() {
+            lines.push(t!("sheet.ranged", weapon = weapon.name(), range = weapon.range, kind = weapon.kind.name()));
+            if weapon.ammo == Ammo::Arrows {
+                lines.push(t!("sheet.arrows", count = self.fleches));
+            }
+        }
+        lines.push(t!("sheet.potions", count = self.potions));
+
+        let resistances = &self.armure.resistances;
+        let names = |types: &[DamageType]| types.iter().map(|kind| kind.name()).collect::<Vec<_>>().join(", ");
+        lines.push(t!("sheet.armor", armor = self.armure.name()));
+        for (key, types) in [("sheet.immune", resistances.immune), ("sheet.resistant", resistances.resistant), ("sheet.weak", resistances.weak)] {
+            if !types.is_empty() {
+                lines.push(t!(key, types = names(types)));
+            }
+        }
+        lines
     }
 
-    pub fn drink_potion(&mut self) {
-        if self.potions > 0 {
-            self.points_de_vie = std::cmp::min(self.points_de_vie + 30, self.max_health);
-            self.potions -= 1;
-            println!("{} boit une potion et récupère 30 points de vie!", self.name);
-        } else {
-            println!("{} n'a plus de potions!", self.name);
+    pub fn ranged_weapon(&self) -> Option<RangedWeapon> {
+        RangedWeapon::for_class(self.espece)
+    }
+
+    pub fn has_ammo(&self, weapon: &RangedWeapon) -> bool {
+        match weapon.ammo {
+            Ammo::Arrows => self.fleches > 0,
+            Ammo::Mana(cost) => self.mana >= cost,
+        }
+    }
+
+    pub fn consume_ammo(&mut self, weapon: &RangedWeapon) {
+        match weapon.ammo {
+            Ammo::Arrows => self.fleches -= 1,
+            Ammo::Mana(cost) => self.mana -= cost,
         }
     }
 
+    pub fn regen_mana(&mut self, amount: i32) {
+        self.mana = std::cmp::min(self.mana + amount, self.max_mana);
+    }
+
     pub fn level_up(&mut self) {
         self.level += 1;
         self.max_health += 20;
@@ -153,8 +290,125 @@ impl Player {
         self.defense += 3;
         self.attaque_speciale = true;
         self.potions += 1;
-        println!("{} monte au niveau {} !", self.name, self.level);
+        if self.espece == Espece::Elfe {
+            self.fleches += 5;
+        }
+        self.mana = self.max_mana;
+    }
+}
 
-    
+impl Combatant for Player {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn health(&self) -> i32 {
+        self.points_de_vie
+    }
+
+    fn max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    fn defense(&self) -> i32 {
+        self.defense
+    }
+
+    fn stats(&self) -> &CombatStats {
+        &self.stats
+    }
+
+    fn take_damage(&mut self, damage: Damage) -> DamageResult {
+        let result = self.armure.resistances.apply(damage);
+        self.points_de_vie = std::cmp::max(0, self.points_de_vie - result.amount);
+        result
+    }
+
+    fn heal(&mut self, amount: i32) -> i32 {
+        let old_hp = self.points_de_vie;
+        self.points_de_vie = std::cmp::min(self.points_de_vie + amount, self.max_health);
+        self.points_de_vie - old_hp
+    }
+
+    fn attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll {
+        let damage = Damage::physical(damage::base_damage(self.attack, target.defense()));
+        damage::roll_attack(rng, &self.stats, target.stats(), damage)
     }
-}
\ No newline at end of file
+
+    fn special_available(&self) -> bool {
+        self.attaque_speciale
+    }
+
+    fn special_attack(&mut self, target: &dyn Combatant, rng: &mut dyn RngCore) -> Roll {
+        let damage = self.special_damage();
+        damage::roll_attack(rng, &self.stats, target.stats(), damage)
+    }
+
+    fn special_range(&self) -> i32 {
+        match self.espece {
+            Espece::Sorciere => 4,
+            Espece::Elfe => 5,
+            _ => 1,
+        }
+    }
+
+    fn drink_potion(&mut self) -> Option<i32> {
+        if self.potions > 0 {
+            self.potions -= 1;
+            Some(self.heal(30))
+        } else {
+            None
+        }
+    }
+
+    // choix par défaut (joueur automatique) : soin si besoin, sinon attaque spéciale puis attaque
+    fn choose_action(&self, _opponent: &dyn Combatant, _rng: &mut dyn RngCore) -> PlayerCombatAction {
+        if self.points_de_vie * 3 < self.max_health && self.potions > 0 {
+            PlayerCombatAction::Drink
+        } else if self.attaque_speciale {
+            PlayerCombatAction::SpecialAttack
+        } else {
+            PlayerCombatAction::Attack
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_name_cases() {
+        let too_long = t!("name.too_long", max = MAX_NAME_LENGTH);
+        let cases: [(&str, std::result::Result<&str, String>); 12] = [
+            ("", Err(t!("name.empty"))),
+            ("   ", Err(t!("name.empty"))),
+            ("  Arthur  ", Ok("Arthur")),
+            ("Jean-Pierre", Ok("Jean-Pierre")),
+            ("D'Artagnan", Ok("D'Artagnan")),
+            ("Élodie 2", Ok("Élodie 2")),
+            ("abcdefghijklmnop", Ok("abcdefghijklmnop")),
+            // la limite compte les caractères, pas les octets
+            ("éééééééééééééééé", Ok("éééééééééééééééé")),
+            ("abcdefghijklmnopq", Err(too_long.clone())),
+            ("  abcdefghijklmnopq  ", Err(too_long)),
+            ("Bob!", Err(t!("name.invalid"))),
+            ("a/b", Err(t!("name.invalid"))),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(validate_name(name), expected.map(String::from), "nom {name:?}");
+        }
+    }
+
+    #[test]
+    fn random_player_uses_the_class_stats() {
+        for _ in 0..20 {
+            let player = Player::new("Test");
+            let reference = Player::new_with_class("Test", player.espece);
+            assert_eq!(
+                (player.max_health, player.attack, player.defense, player.mana, player.fleches),
+                (reference.max_health, reference.attack, reference.defense, reference.mana, reference.fleches),
+            );
+        }
+    }
+}
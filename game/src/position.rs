@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+// Coordonnées d'une case de la carte. Elles ne peuvent pas être négatives : on passe
+// d'une case à sa voisine avec `step`, qui renvoie None au bord gauche ou en haut.
+// Le bord droit et le bas dépendent de la taille de la carte (`Map::contains`).
+// Les champs sont privés : une position se crée avec `new` ou `step` et se lit avec `x()` et `y()`.
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug, Serialize, Deserialize)]
+pub struct Position {
+    x: usize,
+    y: usize,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    // décalage en colonnes et en lignes
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+impl Position {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Position { x, y }
+    }
+
+    pub fn x(self) -> usize {
+        self.x
+    }
+
+    pub fn y(self) -> usize {
+        self.y
+    }
+
+    // case voisine, None si elle sortirait par la gauche ou par le haut
+    pub fn step(self, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.delta();
+        Some(Position {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    // les cases voisines, diagonales comprises
+    pub fn neighbours(self) -> impl Iterator<Item = (Direction, Position)> {
+        Direction::ALL.into_iter().filter_map(move |direction| self.step(direction).map(|next| (direction, next)))
+    }
+
+    // distance en nombre de pas sans diagonale, pour les portées (tirs, sorts, fuite) :
+    // les diagonales ne rallongent pas les portées réglées avant elles
+    pub fn distance(self, other: Position) -> i32 {
+        (self.x.abs_diff(other.x) + self.y.abs_diff(other.y)) as i32
+    }
+
+    // les huit cases voisines, diagonales comprises : assez près pour un coup au contact
+    pub fn is_adjacent(self, other: Position) -> bool {
+        self != other && self.x.abs_diff(other.x) <= 1 && self.y.abs_diff(other.y) <= 1
+    }
+
+    // à portée `range` ; une case voisine l'est toujours, même en diagonale
+    pub fn within(self, other: Position, range: i32) -> bool {
+        self.is_adjacent(other) || self.distance(other) <= range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagonal_is_adjacent_but_ranges_ignore_diagonals() {
+        let from = Position::new(3, 3);
+        let diagonal = Position::new(4, 4);
+        assert!(from.is_adjacent(diagonal));
+        assert_eq!(from.distance(diagonal), 2);
+        assert!(from.within(diagonal, 1));
+        assert!(!from.is_adjacent(from));
+        // la portée reste celle d'avant les diagonales
+        assert!(from.within(Position::new(6, 6), 6));
+        assert!(!from.within(Position::new(7, 6), 6));
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::SpawnTiming;
+use crate::error::Result;
+use crate::game::{CombatMode, Difficulty, Game, GameState, PlayerCombatAction, DEFAULT_MAP_HEIGHT, DEFAULT_MAP_WIDTH};
+use crate::highscores;
+use crate::player::Espece;
+use crate::position::Direction;
+use crate::save;
+use crate::storage;
+use crate::t;
+use crate::tactical::TacticalAction;
+
+// Rejeu d'une partie : la graine, les réglages de départ et la suite des commandes jouées.
+// Le jeu ne tire ses hasards que dans le générateur de la partie, donc rejouer les mêmes
+// commandes depuis la même graine redonne exactement la même partie.
+
+// version 2 : les déplacements sont des directions (diagonales comprises)
+// version 3 : la porte et l'escalier sont placés au hasard, les cartes ne sont plus les mêmes
+// version 4 : les portées se mesurent de nouveau sans diagonale, seul le contact en compte
+const VERSION: u32 = 4;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum Command {
+    Move(Direction),
+    Combat(PlayerCombatAction),
+    Tactical(TacticalAction),
+    StartTargeting,
+    CycleTarget(i32),
+    Fire,
+    CancelTargeting,
+    Tick,
+    // une sauvegarde en cours de partie repart d'une nouvelle graine (voir save.rs)
+    Reseed,
+}
+
+impl Command {
+    // renvoie true si l'affichage a changé
+    pub fn apply(self, game: &mut Game) -> Result<bool> {
+        match self {
+            Command::Move(direction) => {
+                game.move_player(direction)?;
+            },
+            Command::Combat(action) => {
+                game.combat_turn(action)?;
+            },
+            Command::Tactical(action) => {
+                game.tactical_turn(action)?;
+            },
+            Command::StartTargeting => {
+                game.start_targeting();
+            },
+            Command::CycleTarget(step) => game.cycle_target(step),
+            Command::Fire => game.fire_at_target()?,
+            Command::CancelTargeting => game.cancel_targeting(),
+            Command::Tick => return game.tick(),
+            Command::Reseed => {
+                save::reseed(game);
+            },
+        }
+        Ok(true)
+    }
+
+    // action d'exploration : l'horloge avance d'un tick en tour par tour
+    pub fn is_turn(&self) -> bool {
+        matches!(self, Command::Move(_) | Command::Fire)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub seed: u64,
+    pub name: String,
+    pub espece: Espece,
+    pub combat_mode: CombatMode,
+    pub timing: SpawnTiming,
+    pub difficulty: Difficulty,
+    pub map_width: usize,
+    pub map_height: usize,
+    pub commands: Vec<Command>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, name: &str, espece: Espece) -> Self {
+        Replay {
+            version: VERSION,
+            seed,
+            name: name.to_string(),
+            espece,
+            combat_mode: CombatMode::Classic,
+            timing: SpawnTiming::RealTime,
+            difficulty: Difficulty::Normal,
+            map_width: DEFAULT_MAP_WIDTH,
+            map_height: DEFAULT_MAP_HEIGHT,
+            commands: Vec::new(),
+        }
+    }
+
+    // la partie au moment du départ, avant toute commande
+    pub fn game(&self) -> Result<Game> {
+        let mut game = Game::with_map_size(self.seed, self.map_width, self.map_height);
+        game.initialize_player(&self.name, self.espece)?;
+        game.combat_mode = self.combat_mode;
+        game.clock.timing = self.timing;
+        game.difficulty = self.difficulty;
+        Ok(game)
+    }
+
+    pub fn record(&mut self, command: Command) {
+        self.commands.push(command);
+    }
+
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        let replay: Replay = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        if replay.version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                t!("replay.version", version = replay.version),
+            ));
+        }
+        Ok(replay)
+    }
+
+    // écrit le rejeu et renvoie le chemin du fichier
+    pub fn write(&self) -> io::Result<PathBuf> {
+        let dir = storage::data_dir().join("replays");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("replay-{}-{}.json", highscores::today(), self.seed));
+        let contents = serde_json::to_string(self).map_err(io::Error::other)?;
+        storage::write_atomic(&path, &contents)?;
+        Ok(path)
+    }
+}
+
+// rejoue les commandes une à une ; false quand il n'y en a plus ou que la partie est finie
+pub fn step(game: &mut Game, commands: &mut impl Iterator<Item = Command>) -> Result<bool> {
+    if matches!(game.state, GameState::GameOver | GameState::Win) {
+        return Ok(false);
+    }
+    match commands.next() {
+        Some(command) => {
+            command.apply(game)?;
+            Ok(true)
+        },
+        None => Ok(false),
+    }
+}
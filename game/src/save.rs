@@ -0,0 +1,267 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{GameClock, SpawnTiming};
+use crate::damage::CombatStats;
+use crate::dungeon::Dungeon;
+use crate::equipment::Equipment;
+use crate::error::GameError;
+use crate::game::{CombatMode, Difficulty, Game, GameState};
+use crate::map::Map;
+use crate::monster::Monster;
+use crate::player::{Espece, Player};
+use crate::position::Position;
+use crate::stats::RunStats;
+use crate::storage;
+use crate::t;
+
+// Sauvegarde de la partie en cours dans save.json.
+// Le générateur aléatoire ne se sérialise pas : au moment de sauvegarder on le
+// réinitialise avec une graine tirée de lui-même, et c'est cette graine qu'on écrit.
+// La partie continuée et la partie rechargée restent donc identiques.
+
+const FILE_NAME: &str = "save.json";
+// à changer quand le format change : une ancienne sauvegarde est alors refusée
+// version 2 : le donjon garde tous ses étages
+// version 3 : les positions sont des `Position` et les portées se mesurent sans diagonale
+const VERSION: u32 = 3;
+
+// l'armure se déduit de l'espèce, le reste du joueur est recopié tel quel
+#[derive(Serialize, Deserialize)]
+struct SavedPlayer {
+    name: String,
+    #[serde(flatten)]
+    pos: Position,
+    points_de_vie: i32,
+    max_health: i32,
+    level: u32,
+    attack: i32,
+    defense: i32,
+    espece: Espece,
+    attaque_speciale: bool,
+    potions: i32,
+    stats: CombatStats,
+    fleches: i32,
+    mana: i32,
+    max_mana: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveGame {
+    version: u32,
+    seed: u64,
+    rng_seed: u64,
+    player: SavedPlayer,
+    map: Map,
+    monsters: Vec<Monster>,
+    // les autres étages visités
+    dungeon: Dungeon,
+    state: GameState,
+    score: u32,
+    current_monster_index: Option<usize>,
+    combat_mode: CombatMode,
+    #[serde(default)]
+    difficulty: Difficulty,
+    timing: SpawnTiming,
+    ticks: u64,
+    map_width: usize,
+    map_height: usize,
+    stats: RunStats,
+}
+
+pub fn default_path() -> io::Result<PathBuf> {
+    storage::data_file(FILE_NAME)
+}
+
+// tire une nouvelle graine du générateur et repart de celle-ci
+pub fn reseed(game: &mut Game) -> u64 {
+    let rng_seed: u64 = game.rng.gen();
+    game.rng = StdRng::seed_from_u64(rng_seed);
+    rng_seed
+}
+
+pub fn save(game: &mut Game, path: &Path) -> io::Result<()> {
+    // une visée en cours n'est pas sauvegardée
+    if game.state == GameState::Targeting {
+        game.cancel_targeting();
+    }
+    let rng_seed = reseed(game);
+
+    let player = &game.player;
+    let save = SaveGame {
+        version: VERSION,
+        seed: game.seed,
+        rng_seed,
+        player: SavedPlayer {
+            name: player.name.clone(),
+            pos: player.pos,
+            points_de_vie: player.points_de_vie,
+            max_health: player.max_health,
+            level: player.level,
+            attack: player.attack,
+            defense: player.defense,
+            espece: player.espece,
+            attaque_speciale: player.attaque_speciale,
+            potions: player.potions,
+            stats: player.stats,
+            fleches: player.fleches,
+            mana: player.mana,
+            max_mana: player.max_mana,
+        },
+        map: game.map.clone(),
+        monsters: game.monsters.clone(),
+        dungeon: game.dungeon.clone(),
+        state: game.state,
+        score: game.score,
+        current_monster_index: game.current_monster_index,
+        combat_mode: game.combat_mode,
+        difficulty: game.difficulty,
+        timing: game.clock.timing,
+        ticks: game.clock.ticks(),
+        map_width: game.map_width,
+        map_height: game.map_height,
+        stats: game.stats.clone(),
+    };
+    let json = serde_json::to_string(&save).map_err(io::Error::other)?;
+    storage::write_atomic(path, &json)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Une sauvegarde retouchée à la main ne passe ni par `Map::new` ni par `step` : elle pourrait
+// donner une carte dont les lignes n'ont pas la bonne taille, ou poser le joueur, un monstre
+// ou la porte hors de la carte. On la refuse au chargement plutôt que de paniquer en jouant.
+fn check_floor(map: &Map, size: (usize, usize), monsters: &[Monster], player: Option<Position>) -> io::Result<()> {
+    if !map.is_well_formed() || (map.width(), map.height()) != size {
+        return Err(invalid(t!("save.map_size", width = size.0, height = size.1)));
+    }
+    let positions = [Some(map.door()), Some(map.entrance()), map.stairs_up(), player]
+        .into_iter()
+        .flatten()
+        .chain(monsters.iter().map(|monster| monster.pos));
+    for pos in positions {
+        if !map.contains(pos) {
+            return Err(invalid(GameError::OutOfMap(pos).to_string()));
+        }
+    }
+    Ok(())
+}
+
+pub fn load(path: &Path) -> io::Result<Game> {
+    let json = fs::read_to_string(path)?;
+    let save: SaveGame = serde_json::from_str(&json)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    if save.version != VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, t!("save.version", version = save.version, expected = VERSION)));
+    }
+
+    let size = (save.map_width, save.map_height);
+    check_floor(&save.map, size, &save.monsters, Some(save.player.pos))?;
+    for floor in save.dungeon.stored_floors() {
+        check_floor(&floor.map, size, &floor.monsters, None)?;
+    }
+    if let Some(index) = save.current_monster_index.filter(|&index| index >= save.monsters.len()) {
+        return Err(invalid(GameError::NoMonster(index).to_string()));
+    }
+
+    let saved = save.player;
+    let mut game = Game::with_map_size(save.seed, save.map_width, save.map_height);
+    // une taille hors des limites serait ramenée dedans pour les étages suivants
+    if (game.map_width, game.map_height) != size {
+        return Err(invalid(t!("save.map_size", width = size.0, height = size.1)));
+    }
+    game.player = Player {
+        name: saved.name,
+        pos: saved.pos,
+        points_de_vie: saved.points_de_vie,
+        max_health: saved.max_health,
+        level: saved.level,
+        attack: saved.attack,
+        defense: saved.defense,
+        espece: saved.espece,
+        attaque_speciale: saved.attaque_speciale,
+        potions: saved.potions,
+        armure: Equipment::starting_armor(saved.espece),
+        stats: saved.stats,
+        fleches: saved.fleches,
+        mana: saved.mana,
+        max_mana: saved.max_mana,
+    };
+    game.map = save.map;
+    game.monsters = save.monsters;
+    game.dungeon = save.dungeon;
+    game.state = save.state;
+    game.score = save.score;
+    game.current_monster_index = save.current_monster_index;
+    game.combat_mode = save.combat_mode;
+    game.difficulty = save.difficulty;
+    game.clock = GameClock::resume(save.timing, save.ticks);
+    game.stats = save.stats;
+    game.rng = StdRng::seed_from_u64(save.rng_seed);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    // une partie descendue d'un étage, pour avoir aussi un étage rangé dans le donjon
+    fn saved_game(path: &Path) -> Value {
+        let mut game = Game::with_seed(5);
+        game.initialize_player("Test", Espece::Elfe).unwrap();
+        let door = game.map.door();
+        game.take_stairs(door).unwrap();
+        save(&mut game, path).unwrap();
+        assert!(load(path).is_ok());
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    // retouche à la main d'une sauvegarde
+    type Damage = fn(&mut Value);
+
+    #[test]
+    fn damaged_saves_are_refused() {
+        let cases: [(&str, Damage); 9] = [
+            ("joueur hors de la carte", |save| save["player"]["x"] = json!(1000)),
+            ("carte agrandie sans ses cases", |save| {
+                for key in ["map_width", "map_height"] {
+                    save[key] = json!(40);
+                }
+                save["map"]["width"] = json!(40);
+                save["map"]["height"] = json!(40);
+            }),
+            ("largeur de la carte seule", |save| save["map"]["width"] = json!(40)),
+            ("taille de la partie seule", |save| save["map_width"] = json!(40)),
+            ("taille hors limites", |save| {
+                save["map_width"] = json!(500);
+                save["map"]["width"] = json!(500);
+            }),
+            ("ligne de cases trop courte", |save| {
+                save["map"]["tiles"][3].as_array_mut().unwrap().pop();
+            }),
+            ("ligne découverte en moins", |save| {
+                save["map"]["explored"].as_array_mut().unwrap().pop();
+            }),
+            ("étage rangé abîmé", |save| save["dungeon"]["floors"][0]["map"]["height"] = json!(3)),
+            ("monstre combattu inexistant", |save| save["current_monster_index"] = json!(7)),
+        ];
+        for (i, (case, damage)) in cases.into_iter().enumerate() {
+            let path = std::env::temp_dir().join(format!("mini-rpg-save-test-{}-{}.json", std::process::id(), i));
+            let mut json = saved_game(&path);
+            damage(&mut json);
+            fs::write(&path, json.to_string()).unwrap();
+            let result = load(&path);
+            fs::remove_file(&path).unwrap();
+            assert_eq!(result.err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData), "{case}");
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::combat::ActionOutcome;
+use crate::game::PlayerCombatAction;
+use crate::monster::MonsterSpecies;
+
+// Statistiques de la partie en cours, remplies par la logique du jeu
+// et résumées dans le fichier « morgue » en fin de partie.
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct RunStats {
+    // monstres tués, indexés comme MonsterSpecies::ALL
+    pub kills: [u32; 4],
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub potions: u32,
+    pub specials: u32,
+    pub fire_tiles: u32,
+    // actions du joueur par étage (indice 0 = étage 1)
+    pub turns_per_level: Vec<u32>,
+}
+
+impl RunStats {
+    // une action de combat jouée par le joueur
+    pub fn record_action(&mut self, action: &PlayerCombatAction, outcome: &ActionOutcome) {
+        if !outcome.played {
+            return;
+        }
+        self.damage_dealt += outcome.damage;
+        if outcome.potion {
+            self.potions += 1;
+        }
+        if *action == PlayerCombatAction::SpecialAttack {
+            self.specials += 1;
+        }
+    }
+
+    pub fn record_kill(&mut self, species: MonsterSpecies) {
+        self.kills[species as usize] += 1;
+    }
+
+    pub fn record_turn(&mut self, level: u32) {
+        let index = level.saturating_sub(1) as usize;
+        if self.turns_per_level.len() <= index {
+            self.turns_per_level.resize(index + 1, 0);
+        }
+        self.turns_per_level[index] += 1;
+    }
+
+    pub fn total_kills(&self) -> u32 {
+        self.kills.iter().sum()
+    }
+
+    pub fn total_turns(&self) -> u32 {
+        self.turns_per_level.iter().sum()
+    }
+}
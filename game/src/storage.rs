@@ -0,0 +1,101 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::t;
+
+// Fichiers persistants du jeu (scores, etc.) : dans $MINI_RPG_HOME s'il est défini,
+// sinon dans ~/.mini_rpg, sinon dans le dossier courant.
+
+// un verrou plus vieux que ça a été laissé par une partie interrompue
+const STALE_LOCK: Duration = Duration::from_secs(10);
+const LOCK_RETRY: Duration = Duration::from_millis(20);
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("MINI_RPG_HOME") {
+        return PathBuf::from(dir);
+    }
+    match std::env::var_os("HOME") {
+        Some(home) => Path::new(&home).join(".mini_rpg"),
+        None => PathBuf::from("."),
+    }
+}
+
+pub fn data_file(name: &str) -> io::Result<PathBuf> {
+    let dir = data_dir();
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(name))
+}
+
+// Verrou exclusif sur un fichier : un fichier « .lock » créé à côté, supprimé à la libération.
+// Deux parties lancées en même temps écrivent donc chacune à leur tour.
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    pub fn acquire(target: &Path) -> io::Result<FileLock> {
+        let mut path = target.as_os_str().to_owned();
+        path.push(".lock");
+        let path = PathBuf::from(path);
+
+        let started = SystemTime::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(FileLock { path }),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                    let age = fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok());
+                    if age.is_some_and(|age| age > STALE_LOCK) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() > LOCK_TIMEOUT {
+                        return Err(io::Error::new(io::ErrorKind::TimedOut, t!("storage.locked", path = target.display())));
+                    }
+                    thread::sleep(LOCK_RETRY);
+                },
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// ajoute une ligne complète en une seule écriture, sous verrou
+pub fn append_line(path: &Path, line: &str) -> io::Result<()> {
+    let _lock = FileLock::acquire(path)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format!("{}\n", line).as_bytes())?;
+    file.sync_all()
+}
+
+// un fichier absent se lit comme un fichier vide
+pub fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content.lines().map(str::to_string).collect()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+// remplace le fichier d'un coup : on écrit à côté puis on renomme,
+// un arrêt brutal laisse donc l'ancienne version intacte
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let _lock = FileLock::acquire(path)?;
+    let temporary = path.with_extension("tmp");
+    let mut file = File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)
+}
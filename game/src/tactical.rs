@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::GameEvent;
+use crate::combat;
+use crate::combatant::Combatant;
+use crate::error::{GameError, Result};
+use crate::game::{Game, GameState, PlayerCombatAction, Refusal, Turn};
+use crate::position::{Direction, Position};
+use crate::t;
+
+// Combat tactique : le joueur et le monstre se battent directement sur la carte.
+// Les murs bloquent les déplacements et la ligne de vue, les 🔥 brûlent le joueur
+// et arrêtent les monstres.
+
+// Au-delà de cette distance, hors de vue du monstre, le joueur s'est échappé
+const ESCAPE_DISTANCE: i32 = 6;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub enum TacticalAction {
+    Move(Direction),
+    Attack,
+    SpecialAttack,
+    Drink,
+    Wait,
+}
+
+impl Game {
+    pub fn start_tactical_combat(&mut self, monster_idx: usize) -> Result<()> {
+        let monster = self.monster(monster_idx)?;
+        let message = t!("tactical.encounter", monster = monster.species.name(), level = monster.level);
+        self.state = GameState::Combat;
+        self.current_monster_index = Some(monster_idx);
+        self.log.say(message);
+        // comme en combat classique : le suivi des succès repart de zéro pour ce combat
+        self.notify(GameEvent::FightStarted);
+        Ok(())
+    }
+
+    fn in_range(&self, from: Position, to: Position, range: i32) -> bool {
+        from.within(to, range) && self.map.line_of_sight(from, to)
+    }
+
+    pub fn tactical_turn(&mut self, action: TacticalAction) -> Result<Turn> {
+        if self.state != GameState::Combat {
+            return Ok(Turn::Refused(Refusal::WrongState));
+        }
+        let Some(monster_idx) = self.current_monster_index else {
+            return Ok(Turn::Refused(Refusal::WrongState));
+        };
+        let monster = self.monster(monster_idx)?;
+        let player_pos = self.player.pos;
+        let monster_pos = monster.pos;
+
+        // Player's turn
+        match action {
+            TacticalAction::Move(direction) => {
+                let target = self.player.pos.step(direction)
+                    .filter(|&pos| self.map.is_valid_move(pos) && self.find_monster_at(pos).is_none());
+                let Some(pos) = target else {
+                    return Ok(self.refuse(Refusal::Blocked));
+                };
+                self.enter_tile(pos)?;
+                if self.state == GameState::GameOver {
+                    return Ok(Turn::Played);
+                }
+                if self.map.is_on_door(pos) || self.map.is_on_stairs_up(pos) {
+                    self.log.say(t!("tactical.door"));
+                    self.state = GameState::Running;
+                    self.current_monster_index = None;
+                    self.take_stairs(pos)?;
+                    return Ok(Turn::Played);
+                }
+            },
+            TacticalAction::Attack => {
+                if !player_pos.is_adjacent(monster_pos) {
+                    return Ok(self.refuse(Refusal::TooFar));
+                }
+                let monster = &mut self.monsters[monster_idx];
+                let outcome = combat::play_action(&mut self.player, monster, PlayerCombatAction::Attack, &mut self.rng, &mut self.log);
+                self.stats.record_action(&PlayerCombatAction::Attack, &outcome);
+                self.notify(GameEvent::PlayerAction(PlayerCombatAction::Attack));
+            },
+            TacticalAction::SpecialAttack => {
+                let range = self.player.special_range();
+                if !self.in_range(player_pos, monster_pos, range) {
+                    return Ok(self.refuse(Refusal::OutOfRange(range)));
+                }
+                if !self.player.special_available() {
+                    return Ok(self.refuse(Refusal::SpecialUnavailable));
+                }
+                let monster = &mut self.monsters[monster_idx];
+                let outcome = combat::play_action(&mut self.player, monster, PlayerCombatAction::SpecialAttack, &mut self.rng, &mut self.log);
+                self.stats.record_action(&PlayerCombatAction::SpecialAttack, &outcome);
+                self.notify(GameEvent::PlayerAction(PlayerCombatAction::SpecialAttack));
+            },
+            TacticalAction::Drink => {
+                let monster = &mut self.monsters[monster_idx];
+                let outcome = combat::play_action(&mut self.player, monster, PlayerCombatAction::Drink, &mut self.rng, &mut self.log);
+                self.stats.record_action(&PlayerCombatAction::Drink, &outcome);
+                if outcome.played {
+                    self.notify(GameEvent::PlayerAction(PlayerCombatAction::Drink));
+                }
+            },
+            TacticalAction::Wait => self.log.say(t!("tactical.wait", name = self.player.name)),
+        }
+        self.stats.record_turn(self.floor());
+
+        let monster = &self.monsters[monster_idx];
+        if !monster.is_alive() {
+            self.log.say(t!("combat.victory"));
+            self.log.say(t!("combat.experience", points = monster.level * 10));
+            self.end_combat(monster_idx)?;
+            return Ok(Turn::Played);
+        }
+
+        self.monster_tactical_turn(monster_idx)?;
+
+        let monster = &self.monsters[monster_idx];
+        if !self.player.is_alive() {
+            self.log.say(t!("combat.defeat"));
+            self.state = GameState::GameOver;
+            self.death_cause = Some(t!("cause.killed_by", monster = monster.species.name(), level = monster.level));
+            return Ok(Turn::Played);
+        }
+
+        // Le joueur s'est mis hors de portée : le combat s'arrête
+        let player_pos = self.player.pos;
+        let monster_pos = monster.pos;
+        if player_pos.distance(monster_pos) > ESCAPE_DISTANCE && !self.map.line_of_sight(monster_pos, player_pos) {
+            self.log.say(t!("tactical.escaped"));
+            self.state = GameState::Running;
+            self.current_monster_index = None;
+        }
+        Ok(Turn::Played)
+    }
+
+    fn monster_tactical_turn(&mut self, monster_idx: usize) -> Result<()> {
+        let player_pos = self.player.pos;
+        let monster = self.monster(monster_idx)?;
+        let monster_pos = monster.pos;
+        let range = monster.special_range();
+        let special_available = monster.special_available();
+
+        self.log.say(t!("combat.monster_turn"));
+        if player_pos.is_adjacent(monster_pos) {
+            let monster = &mut self.monsters[monster_idx];
+            let action = monster.choose_action(&self.player, &mut self.rng);
+            let outcome = combat::play_action(monster, &mut self.player, action, &mut self.rng, &mut self.log);
+            self.stats.damage_taken += outcome.damage;
+        } else if range > 1 && special_available && self.in_range(monster_pos, player_pos, range) {
+            let monster = &mut self.monsters[monster_idx];
+            let outcome = combat::play_action(monster, &mut self.player, PlayerCombatAction::SpecialAttack, &mut self.rng, &mut self.log);
+            self.stats.damage_taken += outcome.damage;
+        } else {
+            self.move_monster_towards_player(monster_idx)?;
+        }
+        Ok(())
+    }
+
+    // Le monstre avance d'une case vers le joueur, diagonales comprises. Il contourne les murs
+    // et ne s'aventure jamais dans les 🔥 : le joueur peut s'en servir pour lui barrer la route.
+    fn move_monster_towards_player(&mut self, monster_idx: usize) -> Result<()> {
+        let from = self.monster(monster_idx)?.pos;
+        let best = self.monster_first_step(from);
+
+        let monster = self.monsters.get_mut(monster_idx).ok_or(GameError::NoMonster(monster_idx))?;
+        match best {
+            Some(next) => {
+                monster.pos = next;
+                self.map.clear_position(from)?;
+                self.map.place_monster(next)?;
+                self.log.say(t!("tactical.approaches", monster = monster.species.name()));
+            },
+            None => self.log.say(t!("tactical.no_path", monster = monster.species.name())),
+        }
+        Ok(())
+    }
+
+    // Premier pas du plus court chemin du monstre vers le joueur, à travers les cases vides
+    // (ni mur, ni 🔥, ni autre monstre). None si le joueur est hors d'atteinte.
+    fn monster_first_step(&self, from: Position) -> Option<Position> {
+        let player_pos = self.player.pos;
+        let (width, height) = (self.map.width(), self.map.height());
+        let mut first: Vec<Vec<Option<Position>>> = vec![vec![None; width]; height];
+        let mut visited = vec![vec![false; width]; height];
+        let mut queue = VecDeque::new();
+        visited[from.y()][from.x()] = true;
+        queue.push_back(from);
+
+        while let Some(pos) = queue.pop_front() {
+            for (_, next) in pos.neighbours() {
+                let step = if pos == from { Some(next) } else { first[pos.y()][pos.x()] };
+                if next == player_pos {
+                    return step;
+                }
+                if !self.map.contains(next) || visited[next.y()][next.x()] || !self.map.is_empty(next) {
+                    continue;
+                }
+                visited[next.y()][next.x()] = true;
+                first[next.y()][next.x()] = step;
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::Achievement;
+    use crate::log::MessageLog;
+    use crate::map::Map;
+    use crate::monster::{Monster, MonsterSpecies};
+    use crate::player::Espece;
+
+    // une carte écrite à la main, une chaîne par ligne
+    fn map_from_rows(rows: &[&str]) -> Map {
+        let tiles: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        let (width, height) = (tiles[0].len(), tiles.len());
+        serde_json::from_value(serde_json::json!({
+            "width": width,
+            "height": height,
+            "tiles": tiles,
+            "explored": vec![vec![false; width]; height],
+            "door": { "x": 0, "y": 0 },
+            "stairs_up": null,
+            "entrance": { "x": 0, "y": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn game_on(rows: &[&str], player: Position) -> Game {
+        let mut game = Game::with_seed(1);
+        game.map = map_from_rows(rows);
+        game.player.pos = player;
+        game
+    }
+
+    #[test]
+    fn tactical_fight_tracks_achievements() {
+        let mut game = Game::with_seed(4);
+        game.initialize_player("Test", Espece::Chevalier).unwrap();
+        game.log = MessageLog::silent();
+        // une potion bue avant ce combat ne doit pas compter
+        game.notify(GameEvent::PlayerAction(PlayerCombatAction::Drink));
+
+        let pos = game.player.pos.neighbours().map(|(_, pos)| pos).find(|&pos| game.map.is_empty(pos)).expect("case libre");
+        let mut monster = Monster::with_species(pos, MonsterSpecies::Goblin, 1);
+        monster.health = 1;
+        game.map.place_monster(pos).unwrap();
+        game.monsters.push(monster);
+        game.start_tactical_combat(game.monsters.len() - 1).unwrap();
+        while game.state == GameState::Combat {
+            game.tactical_turn(TacticalAction::Attack).unwrap();
+        }
+        assert!(game.achievements.is_unlocked(Achievement::BasicOnly));
+    }
+
+    #[test]
+    fn monster_goes_around_walls() {
+        // le mur en colonne 3 cache le joueur : aller tout droit ne mène nulle part
+        let game = game_on(&["⬛⬛⬛⬛⬛", "⬛⬛⬛⬜⬛", "⬛⬛⬛⬜⬛", "⬛⬛⬛⬜⬛", "⬛⬛⬛⬛⬛"], Position::new(4, 2));
+        let step = game.monster_first_step(Position::new(2, 2));
+        assert!(matches!(step, Some(pos) if pos == Position::new(2, 1) || pos == Position::new(2, 3)), "{step:?}");
+    }
+
+    #[test]
+    fn monster_never_walks_through_fire() {
+        // les 🔥 ferment le seul passage : le monstre reste sur place
+        let game = game_on(&["⬛⬜⬛⬛⬛", "⬛🔥⬛⬛⬛", "⬛⬜⬛⬛⬛"], Position::new(3, 1));
+        assert_eq!(game.monster_first_step(Position::new(0, 1)), None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::combat;
+use crate::combatant::Combatant;
+use crate::damage::{self, Damage};
+use crate::equipment::{Ammo, RangedWeapon};
+use crate::error::{GameError, Result};
+use crate::game::{Game, GameState, Refusal, Turn};
+use crate::t;
+
+// Mode visée : l'Elfe tire à l'arc, la Sorcière lance un sort sur un monstre visible,
+// sans engager de combat au corps à corps.
+pub struct Targeting {
+    pub weapon: RangedWeapon,
+    // indices des monstres visibles à portée, du plus proche au plus lointain
+    pub targets: Vec<usize>,
+    pub selected: usize,
+}
+
+impl Game {
+    pub fn start_targeting(&mut self) -> Turn {
+        if self.state != GameState::Running {
+            return Turn::Refused(Refusal::WrongState);
+        }
+        let Some(weapon) = self.player.ranged_weapon() else {
+            return self.refuse(Refusal::NoWeapon);
+        };
+        if !self.player.has_ammo(&weapon) {
+            return match weapon.ammo {
+                Ammo::Arrows => self.refuse(Refusal::NoArrows),
+                Ammo::Mana(cost) => self.refuse(Refusal::NoMana(cost)),
+            };
+        }
+
+        let player_pos = self.player.pos;
+        let mut targets: Vec<usize> = (0..self.monsters.len())
+            .filter(|&i| {
+                let monster_pos = self.monsters[i].pos;
+                player_pos.within(monster_pos, weapon.range)
+                    && self.map.line_of_sight(player_pos, monster_pos)
+            })
+            .collect();
+        if targets.is_empty() {
+            return self.refuse(Refusal::NoTarget(weapon.range));
+        }
+        targets.sort_by_key(|&i| player_pos.distance(self.monsters[i].pos));
+
+        self.targeting = Some(Targeting { weapon, targets, selected: 0 });
+        self.state = GameState::Targeting;
+        Turn::Played
+    }
+
+    // passe à la cible suivante (step = 1) ou précédente (step = -1)
+    pub fn cycle_target(&mut self, step: i32) {
+        if let Some(targeting) = &mut self.targeting {
+            let count = targeting.targets.len() as i32;
+            targeting.selected = (targeting.selected as i32 + step).rem_euclid(count) as usize;
+        }
+    }
+
+    pub fn selected_target(&self) -> Option<usize> {
+        self.targeting.as_ref().map(|t| t.targets[t.selected])
+    }
+
+    pub fn cancel_targeting(&mut self) {
+        self.targeting = None;
+        if self.state == GameState::Targeting {
+            self.state = GameState::Running;
+        }
+    }
+
+    pub fn fire_at_target(&mut self) -> Result<()> {
+        let Some(targeting) = self.targeting.take() else {
+            return Ok(());
+        };
+        self.state = GameState::Running;
+
+        let weapon = targeting.weapon;
+        let monster_idx = targeting.targets[targeting.selected];
+        self.player.consume_ammo(&weapon);
+
+        let floor = self.floor();
+        let monster = self.monsters.get_mut(monster_idx).ok_or(GameError::NoMonster(monster_idx))?;
+        let amount = damage::base_damage(self.player.attack, monster.defense);
+        let roll = damage::roll_attack(&mut self.rng, &self.player.stats, &monster.stats, Damage::new(amount, weapon.kind));
+
+        self.log.say(t!("targeting.fire", name = self.player.name, weapon = weapon.name(), monster = monster.species.name()));
+        self.stats.damage_dealt += combat::apply_roll(&self.player, monster, roll, &mut self.log);
+        self.stats.record_turn(floor);
+
+        if !monster.is_alive() {
+            self.log.say(t!("targeting.killed", monster = monster.species.name(), points = monster.level * 10));
+            self.score += monster.level * 10;
+            self.stats.record_kill(monster.species);
+            let monster = self.monsters.remove(monster_idx);
+            self.map.clear_position(monster.pos)?;
+        }
+        Ok(())
+    }
+}
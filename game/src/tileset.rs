@@ -0,0 +1,182 @@
+use crossterm::style::Color;
+
+use crate::game::Game;
+use crate::monster::MonsterSpecies;
+use crate::player::Espece;
+use crate::position::Position;
+use crate::t;
+
+// Jeux de tuiles : la carte garde ses icônes, seul l'affichage les traduit.
+// Les emojis n'ont pas la même largeur partout ; le jeu ASCII reste aligné
+// sur n'importe quel terminal.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Floor,
+    Wall,
+    Door,
+    StairsUp,
+    Health,
+    Fire,
+    Player(Espece),
+    Monster(MonsterSpecies),
+    Target,
+    Potion,
+}
+
+impl Tile {
+    // ce qui se trouve sur une case de la carte de la partie
+    pub fn at(game: &Game, pos: Position) -> Tile {
+        match game.map.tile(pos) {
+            '⬜' => Tile::Wall,
+            '🚪' => Tile::Door,
+            '🔼' => Tile::StairsUp,
+            '🍗' => Tile::Health,
+            '🔥' => Tile::Fire,
+            '👾' => match game.find_monster_at(pos) {
+                Some(i) => Tile::Monster(game.monsters[i].species),
+                None => Tile::Monster(MonsterSpecies::Goblin),
+            },
+            '⬛' => Tile::Floor,
+            _ => Tile::Player(game.player.espece),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tileset {
+    Emoji,
+    Ascii,
+    // ASCII avec une palette lisible pour les daltoniens (Okabe-Ito)
+    ColorBlind,
+}
+
+// palette Okabe-Ito
+const ORANGE: Color = Color::Rgb { r: 230, g: 159, b: 0 };
+const SKY_BLUE: Color = Color::Rgb { r: 86, g: 180, b: 233 };
+const BLUISH_GREEN: Color = Color::Rgb { r: 0, g: 158, b: 115 };
+const YELLOW: Color = Color::Rgb { r: 240, g: 228, b: 66 };
+const BLUE: Color = Color::Rgb { r: 0, g: 114, b: 178 };
+const VERMILLION: Color = Color::Rgb { r: 213, g: 94, b: 0 };
+const PURPLE: Color = Color::Rgb { r: 204, g: 121, b: 167 };
+
+impl Tileset {
+    pub const ALL: [Tileset; 3] = [Tileset::Emoji, Tileset::Ascii, Tileset::ColorBlind];
+
+    // nom accepté en ligne de commande
+    pub fn id(&self) -> &'static str {
+        match self {
+            Tileset::Emoji => "emoji",
+            Tileset::Ascii => "ascii",
+            Tileset::ColorBlind => "daltonien",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Tileset::Emoji => t!("tileset.emoji"),
+            Tileset::Ascii => t!("tileset.ascii"),
+            Tileset::ColorBlind => t!("tileset.color_blind"),
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Tileset> {
+        Tileset::ALL.into_iter().find(|tileset| tileset.id() == name)
+    }
+
+    pub fn next(&self) -> Tileset {
+        match self {
+            Tileset::Emoji => Tileset::Ascii,
+            Tileset::Ascii => Tileset::ColorBlind,
+            Tileset::ColorBlind => Tileset::Emoji,
+        }
+    }
+
+    // colonnes occupées par une case, espace de séparation compris
+    pub fn cell_width(&self) -> usize {
+        match self {
+            Tileset::Emoji => 3,
+            Tileset::Ascii | Tileset::ColorBlind => 2,
+        }
+    }
+
+    pub fn glyph(&self, tile: Tile) -> String {
+        match self {
+            Tileset::Emoji => match tile {
+                Tile::Floor => "⬛".to_string(),
+                Tile::Wall => "⬜".to_string(),
+                Tile::Door => "🚪".to_string(),
+                Tile::StairsUp => "🔼".to_string(),
+                Tile::Health => "🍗".to_string(),
+                Tile::Fire => "🔥".to_string(),
+                Tile::Player(espece) => espece.icon().to_string(),
+                Tile::Monster(_) => "👾".to_string(),
+                Tile::Target => "🎯".to_string(),
+                Tile::Potion => "🧪".to_string(),
+            },
+            Tileset::Ascii | Tileset::ColorBlind => match tile {
+                Tile::Floor => ".".to_string(),
+                Tile::Wall => "#".to_string(),
+                Tile::Door => ">".to_string(),
+                Tile::StairsUp => "<".to_string(),
+                Tile::Health => "%".to_string(),
+                Tile::Fire => "^".to_string(),
+                Tile::Player(_) => "@".to_string(),
+                Tile::Monster(species) => species.letter().to_string(),
+                Tile::Target => "X".to_string(),
+                Tile::Potion => "!".to_string(),
+            },
+        }
+    }
+
+    // couleur d'une jauge de points de vie : pleine, entamée ou presque vide
+    pub fn gauge_color(&self, value: i32, max: i32) -> Color {
+        let (full, half, low) = match self {
+            Tileset::ColorBlind => (BLUE, YELLOW, VERMILLION),
+            _ => (Color::Green, Color::Yellow, Color::Red),
+        };
+        if value * 3 <= max {
+            low
+        } else if value * 3 <= max * 2 {
+            half
+        } else {
+            full
+        }
+    }
+
+    // les emojis ont leurs propres couleurs
+    pub fn color(&self, tile: Tile) -> Option<Color> {
+        match self {
+            Tileset::Emoji => None,
+            Tileset::Ascii => Some(match tile {
+                Tile::Floor => Color::DarkGrey,
+                Tile::Wall => Color::Grey,
+                Tile::Door | Tile::StairsUp => Color::Yellow,
+                Tile::Health => Color::Green,
+                Tile::Fire => Color::Red,
+                Tile::Player(_) => Color::White,
+                Tile::Monster(MonsterSpecies::Goblin) => Color::Green,
+                Tile::Monster(MonsterSpecies::Orc) => Color::DarkYellow,
+                Tile::Monster(MonsterSpecies::Skeleton) => Color::Grey,
+                Tile::Monster(MonsterSpecies::Dragon) => Color::Red,
+                Tile::Target => Color::Magenta,
+                Tile::Potion => Color::Magenta,
+            }),
+            // ni rouge/vert ni teintes proches pour distinguer soins et dangers
+            Tileset::ColorBlind => Some(match tile {
+                Tile::Floor => Color::DarkGrey,
+                Tile::Wall => Color::Grey,
+                Tile::Door | Tile::StairsUp => YELLOW,
+                Tile::Health => SKY_BLUE,
+                Tile::Fire => ORANGE,
+                Tile::Player(_) => Color::White,
+                Tile::Monster(MonsterSpecies::Goblin) => BLUISH_GREEN,
+                Tile::Monster(MonsterSpecies::Orc) => VERMILLION,
+                Tile::Monster(MonsterSpecies::Skeleton) => Color::White,
+                Tile::Monster(MonsterSpecies::Dragon) => PURPLE,
+                Tile::Target => BLUE,
+                Tile::Potion => SKY_BLUE,
+            }),
+        }
+    }
+}
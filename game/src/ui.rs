@@ -0,0 +1,352 @@
+use std::io::{self, Stdout, Write};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::{
+    cursor,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+    QueueableCommand,
+};
+
+use crate::equipment::Ammo;
+use crate::game::Game;
+use crate::position::Position;
+use crate::t;
+use crate::tileset::{Tile, Tileset};
+
+// Interface plein écran : la carte à gauche, le panneau du joueur à droite,
+// le journal des messages en dessous et la ligne de commandes tout en bas.
+// Chaque image est écrite par-dessus la précédente (sans effacer tout l'écran)
+// puis envoyée d'un coup, ce qui évite le clignotement.
+
+const BAR_WIDTH: usize = 12;
+const OVERLAY_WIDTH: usize = 64;
+// colonnes réservées au panneau latéral et lignes minimales du journal
+const SIDE_WIDTH: usize = 38;
+const MIN_LOG_LINES: usize = 5;
+// taille maximale de la mini-carte, bordure non comprise
+const MINIMAP_WIDTH: usize = 40;
+const MINIMAP_HEIGHT: usize = 16;
+
+// un écran est ouvert : il faudra rendre le terminal, même après une panique
+static OPEN: AtomicBool = AtomicBool::new(false);
+
+// Rend le terminal tel qu'on l'a trouvé : mode brut coupé, curseur visible, écran normal.
+// Sans effet si aucun écran n'est ouvert ; les erreurs sont ignorées, on ne peut plus rien afficher.
+pub fn restore_terminal() {
+    if !OPEN.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let _ = terminal::disable_raw_mode();
+    let mut out = io::stdout();
+    let _ = out.queue(ResetColor).and_then(|out| out.queue(cursor::Show)).and_then(|out| out.queue(terminal::LeaveAlternateScreen));
+    let _ = out.flush();
+}
+
+// Une panique rend d'abord le terminal, sinon son message serait perdu dans l'écran alternatif.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+}
+
+// fenêtre encadrée affichée par-dessus la carte (inventaire, pause, question...)
+pub struct Overlay<'a> {
+    pub title: &'a str,
+    pub lines: &'a [String],
+}
+
+// ce que la boucle principale veut montrer en plus de l'état du jeu
+pub struct View<'a> {
+    // nombre de messages du journal déjà dévoilés
+    pub shown: usize,
+    // nombre de lignes remontées dans le journal (0 = les plus récentes)
+    pub scroll: usize,
+    pub overlay: Option<Overlay<'a>>,
+    pub commands: &'a str,
+    pub tileset: Tileset,
+    pub minimap: bool,
+}
+
+// partie de la carte visible à l'écran, centrée sur le joueur autant que possible
+struct Camera {
+    x: usize,
+    y: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl Camera {
+    fn follow(game: &Game, columns: usize, rows: usize) -> Camera {
+        let columns = columns.clamp(1, game.map.width());
+        let rows = rows.clamp(1, game.map.height());
+        let center = |player: usize, view: usize, size: usize| player.saturating_sub(view / 2).min(size - view);
+        Camera {
+            x: center(game.player.pos.x(), columns, game.map.width()),
+            y: center(game.player.pos.y(), rows, game.map.height()),
+            columns,
+            rows,
+        }
+    }
+}
+
+pub struct Screen {
+    out: Stdout,
+}
+
+fn bar(value: i32, max: i32) -> String {
+    let filled = if max > 0 { (value.max(0) as usize * BAR_WIDTH).div_ceil(max as usize) } else { 0 };
+    let filled = filled.min(BAR_WIDTH);
+    format!("[{}{}] {}/{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled), value.max(0), max)
+}
+
+// coupe une ligne à la largeur disponible (approximatif pour les emojis)
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+// les lignes du panneau latéral, avec leur couleur éventuelle
+fn side_panel(game: &Game, tileset: Tileset) -> Vec<(String, Option<Color>)> {
+    let player = &game.player;
+    let health_color = |value, max| Some(tileset.gauge_color(value, max));
+    let mut lines = vec![
+        (format!("{} {} — {}", tileset.glyph(Tile::Player(player.espece)), player.name, player.espece.name()), None),
+        (t!("panel.level", level = player.level, score = game.score), None),
+        (String::new(), None),
+        (t!("panel.health", bar = bar(player.points_de_vie, player.max_health)), health_color(player.points_de_vie, player.max_health)),
+    ];
+    if let Some(weapon) = player.ranged_weapon() {
+        match weapon.ammo {
+            Ammo::Arrows => lines.push((t!("panel.arrows", count = player.fleches), None)),
+            Ammo::Mana(_) => lines.push((t!("panel.mana", bar = bar(player.mana, player.max_mana)), Some(Color::Blue))),
+        }
+    }
+    let potions = tileset.glyph(Tile::Potion).repeat(player.potions.clamp(0, 5) as usize);
+    lines.push((t!("panel.potions", icons = potions, count = player.potions), None));
+    if player.attaque_speciale {
+        lines.push((t!("panel.special_ready"), Some(Color::Cyan)));
+    } else {
+        lines.push((t!("panel.special_used"), Some(Color::DarkGrey)));
+    }
+    lines.push((t!("panel.armor", armor = player.armure.name()), None));
+
+    // un index périmé n'empêche pas de dessiner le reste du panneau
+    if let Some(monster) = game.current_monster_index.and_then(|i| game.monsters.get(i)) {
+        lines.push((String::new(), None));
+        lines.push((t!("panel.fighting", monster = monster.species.name(), level = monster.level), Some(Color::Red)));
+        lines.push((t!("panel.health", bar = bar(monster.health, monster.max_health)), health_color(monster.health, monster.max_health)));
+    }
+
+    if let (Some(targeting), Some(monster)) = (&game.targeting, game.selected_target().and_then(|i| game.monsters.get(i))) {
+        lines.push((String::new(), None));
+        lines.push((format!("🎯 {} [{}/{}]", targeting.weapon.name(), targeting.selected + 1, targeting.targets.len()), Some(Color::Yellow)));
+        lines.push((t!("panel.monster", monster = monster.species.name(), level = monster.level), None));
+        lines.push((t!("panel.health", bar = bar(monster.health, monster.max_health)), health_color(monster.health, monster.max_health)));
+    }
+
+    lines.push((String::new(), None));
+    lines.push((t!("panel.tick", tick = game.clock.ticks(), timing = game.clock.timing.name()), Some(Color::DarkGrey)));
+    lines
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+impl Screen {
+    // Passe sur l'écran alternatif. Le terminal est rendu quand l'écran est lâché,
+    // y compris sur une erreur remontée par `?` ou une panique.
+    pub fn open() -> io::Result<Screen> {
+        let mut out = io::stdout();
+        OPEN.store(true, Ordering::SeqCst);
+        out.queue(terminal::EnterAlternateScreen)?
+            .queue(cursor::Hide)?
+            .queue(terminal::Clear(ClearType::All))?;
+        out.flush()?;
+        Ok(Screen { out })
+    }
+
+    // écran du jeu : en plus, les touches arrivent une à une (mode brut)
+    pub fn open_raw() -> io::Result<Screen> {
+        let screen = Screen::open()?;
+        terminal::enable_raw_mode()?;
+        Ok(screen)
+    }
+
+    // après un redimensionnement, des restes de l'ancienne image peuvent traîner
+    pub fn clear(&mut self) -> io::Result<()> {
+        self.out.queue(terminal::Clear(ClearType::All))?;
+        self.out.flush()
+    }
+
+    fn line(&mut self, x: u16, y: u16, text: &str, color: Option<Color>) -> io::Result<()> {
+        self.out.queue(cursor::MoveTo(x, y))?;
+        if let Some(color) = color {
+            self.out.queue(SetForegroundColor(color))?;
+        }
+        self.out.queue(Print(text))?;
+        if color.is_some() {
+            self.out.queue(ResetColor)?;
+        }
+        Ok(())
+    }
+
+    pub fn draw(&mut self, game: &Game, view: &View) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+
+        let tileset = view.tileset;
+        let target = game.selected_target().and_then(|i| game.monsters.get(i)).map(|monster| monster.pos);
+        // la mise en page est recalculée à chaque image : un redimensionnement suffit à la refaire
+        let camera = Camera::follow(
+            game,
+            width.saturating_sub(SIDE_WIDTH + 2) / tileset.cell_width(),
+            height.saturating_sub(MIN_LOG_LINES + 3),
+        );
+        let side_x = (camera.columns * tileset.cell_width() + 2) as u16;
+        let side = side_panel(game, tileset);
+
+        let mut title = t!("screen.title", level = game.floor());
+        if camera.columns < game.map.width() || camera.rows < game.map.height() {
+            title.push_str(&t!("screen.map_position", width = game.map.width(), height = game.map.height(), x = game.player.pos.x(), y = game.player.pos.y()));
+        }
+        self.line(0, 0, &fit(&title, width), Some(Color::Yellow))?;
+        self.out.queue(terminal::Clear(ClearType::UntilNewLine))?;
+
+        let body_height = camera.rows.max(side.len());
+        for row in 0..body_height {
+            let y = (row + 1) as u16;
+            if y as usize >= height {
+                break;
+            }
+            self.out.queue(cursor::MoveTo(0, y))?.queue(terminal::Clear(ClearType::UntilNewLine))?;
+            if row < camera.rows {
+                let map_y = camera.y + row;
+                for column in 0..camera.columns {
+                    let pos = Position::new(camera.x + column, map_y);
+                    let tile = if target == Some(pos) { Tile::Target } else { Tile::at(game, pos) };
+                    self.line((column * tileset.cell_width()) as u16, y, &tileset.glyph(tile), tileset.color(tile))?;
+                }
+            }
+            if let Some((text, color)) = side.get(row) {
+                self.line(side_x, y, &fit(text, width.saturating_sub(side_x as usize)), *color)?;
+            }
+        }
+
+        // journal : toutes les lignes restantes sauf la dernière (commandes)
+        let log_top = body_height + 2;
+        let log_lines = height.saturating_sub(log_top + 1);
+        let header = if view.scroll > 0 { t!("screen.log_scrolled", lines = view.scroll) } else { t!("screen.log") };
+        if log_top < height {
+            self.line(0, (log_top - 1) as u16, &header, Some(Color::DarkGrey))?;
+            self.out.queue(terminal::Clear(ClearType::UntilNewLine))?;
+        }
+        let upto = view.shown.saturating_sub(view.scroll);
+        let messages = game.log.recent(upto, log_lines);
+        for row in 0..log_lines {
+            let y = (log_top + row) as u16;
+            self.out.queue(cursor::MoveTo(0, y))?.queue(terminal::Clear(ClearType::UntilNewLine))?;
+            if let Some(message) = messages.get(row) {
+                self.line(0, y, &fit(message.trim_start_matches('\n'), width), None)?;
+            }
+        }
+
+        if height > 0 {
+            let y = (height - 1) as u16;
+            self.out.queue(cursor::MoveTo(0, y))?.queue(terminal::Clear(ClearType::UntilNewLine))?;
+            self.line(0, y, &fit(view.commands, width), Some(Color::Cyan))?;
+        }
+
+        if view.minimap {
+            self.minimap(game)?;
+        }
+
+        if let Some(overlay) = &view.overlay {
+            self.overlay(overlay.title, overlay.lines, width, height)?;
+        }
+
+        self.out.flush()
+    }
+
+    // Page de menu sans partie affichée : un titre, des lignes, les commandes en bas
+    pub fn page(&mut self, title: &str, lines: &[String], commands: &str) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+
+        self.out.queue(terminal::Clear(ClearType::All))?;
+        self.line(2, 1, &fit(title, width.saturating_sub(2)), Some(Color::Yellow))?;
+        for (row, text) in lines.iter().take(height.saturating_sub(5)).enumerate() {
+            self.line(2, (row + 3) as u16, &fit(text, width.saturating_sub(2)), None)?;
+        }
+        if height > 0 {
+            self.line(0, (height - 1) as u16, &fit(commands, width), Some(Color::Cyan))?;
+        }
+        self.out.flush()
+    }
+
+    // Mini-carte des cases découvertes, en haut à gauche par-dessus la carte.
+    // Chaque caractère résume un bloc de cases sur les grandes cartes.
+    fn minimap(&mut self, game: &Game) -> io::Result<()> {
+        let map = &game.map;
+        let block_x = map.width().div_ceil(MINIMAP_WIDTH);
+        let block_y = map.height().div_ceil(MINIMAP_HEIGHT);
+        let columns = map.width().div_ceil(block_x);
+        let rows = map.height().div_ceil(block_y);
+
+        let label = format!(" {} ", t!("screen.minimap"));
+        let inner = columns.max(label.chars().count());
+
+        self.line(0, 1, &format!("┌{}{}┐", label, "─".repeat(inner - label.chars().count())), Some(Color::Yellow))?;
+        for row in 0..rows {
+            let mut text = String::new();
+            for column in 0..columns {
+                let xs = column * block_x..((column + 1) * block_x).min(map.width());
+                let ys = row * block_y..((row + 1) * block_y).min(map.height());
+                let seen: Vec<Position> = ys
+                    .flat_map(|y| xs.clone().map(move |x| Position::new(x, y)))
+                    .filter(|&pos| map.is_explored(pos))
+                    .collect();
+                let player = game.player.pos;
+                let symbol = if xs.contains(&player.x()) && (row * block_y..(row + 1) * block_y).contains(&player.y()) {
+                    '@'
+                } else if seen.iter().any(|&pos| map.is_on_door(pos)) {
+                    '>'
+                } else if seen.iter().any(|&pos| map.is_on_stairs_up(pos)) {
+                    '<'
+                } else if seen.iter().any(|&pos| map.is_wall(pos)) {
+                    '#'
+                } else if !seen.is_empty() {
+                    '.'
+                } else {
+                    ' '
+                };
+                text.push(symbol);
+            }
+            self.line(0, 2 + row as u16, &format!("│{}{}│", text, " ".repeat(inner - columns)), None)?;
+        }
+        self.line(0, 2 + rows as u16, &format!("└{}┘", "─".repeat(inner)), Some(Color::Yellow))
+    }
+
+    // fenêtre encadrée centrée sur l'écran
+    fn overlay(&mut self, title: &str, lines: &[String], width: usize, height: usize) -> io::Result<()> {
+        let inner = OVERLAY_WIDTH.min(width.saturating_sub(4));
+        let visible = lines.len().min(height.saturating_sub(4));
+        let x = (width.saturating_sub(inner + 4) / 2) as u16;
+        let y = (height.saturating_sub(visible + 2) / 2) as u16;
+
+        let top = format!("┌{}{}┐", title, "─".repeat((inner + 2).saturating_sub(title.chars().count())));
+        self.line(x, y, &top, Some(Color::Yellow))?;
+        for (row, text) in lines.iter().take(visible).enumerate() {
+            let text = fit(text, inner);
+            let padding = " ".repeat(inner - text.chars().count());
+            self.line(x, y + 1 + row as u16, &format!("│ {}{} │", text, padding), None)?;
+        }
+        let bottom = format!("└{}┘", "─".repeat(inner + 2));
+        self.line(x, y + 1 + visible as u16, &bottom, Some(Color::Yellow))
+    }
+}